@@ -0,0 +1,308 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+
+use crate::accessibility::Accessibility;
+use crate::localization::Localization;
+use crate::localization::LocalizedText;
+use crate::save::SaveData;
+use crate::widgets::set_button_label;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonPressedEvent;
+use crate::widgets::ButtonSpec;
+use crate::widgets::MenuButton;
+use crate::widgets::UiTheme;
+use crate::AppState;
+use crate::GameDifficultyState;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityOption {
+    Palette,
+    Contrast,
+    CrosshairSize,
+    Flash,
+    Motion,
+    AimAssist,
+    Language,
+}
+
+//shows the selected palette's difficulty colors
+#[derive(Component)]
+pub struct PaletteSwatch(pub GameDifficultyState);
+
+#[derive(Component)]
+pub struct AccessibilityEntity;
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+    accessibility: Res<Accessibility>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    info!("Accessibility menu activated");
+
+    let font = localization.font(&asset_server);
+
+    //create full screen node bundle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(AccessibilityEntity)
+        //create yellow title background
+        .with_children(|parent| {
+            parent
+                .spawn(title_panel(&theme, 800.0, 100.0))
+                //"accessibility" text
+                .with_children(|parent| {
+                    parent.spawn((
+                        text(
+                            localization.text("accessibility"),
+                            &font,
+                            90.0,
+                            theme.title_text,
+                        ),
+                        LocalizedText("accessibility"),
+                    ));
+                });
+        })
+        //one row per option: its name and a button cycling through the values
+        .with_children(|parent| {
+            for option in [
+                AccessibilityOption::Palette,
+                AccessibilityOption::Contrast,
+                AccessibilityOption::CrosshairSize,
+                AccessibilityOption::Flash,
+                AccessibilityOption::Motion,
+                AccessibilityOption::AimAssist,
+                AccessibilityOption::Language,
+            ] {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(800.0),
+                            height: Val::Px(58.0),
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::SpaceBetween,
+                            padding: UiRect::horizontal(Val::Px(20.0)),
+                            column_gap: Val::Px(15.0),
+                            ..default()
+                        },
+                        background_color: Color::BLACK.into(),
+                        ..default()
+                    })
+                    //option name
+                    .with_children(|parent| {
+                        parent.spawn((
+                            text(
+                                localization.text(option_label(option)),
+                                &font,
+                                32.0,
+                                theme.text,
+                            ),
+                            LocalizedText(option_label(option)),
+                        ));
+                    })
+                    //palette preview
+                    .with_children(|parent| {
+                        if option != AccessibilityOption::Palette {
+                            return;
+                        }
+
+                        for difficulty in [
+                            GameDifficultyState::Easy,
+                            GameDifficultyState::Medium,
+                            GameDifficultyState::Hard,
+                        ] {
+                            parent.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(40.0),
+                                        height: Val::Px(40.0),
+                                        ..default()
+                                    },
+                                    background_color: accessibility
+                                        .palette
+                                        .difficulty_color(difficulty)
+                                        .into(),
+                                    ..default()
+                                },
+                                PaletteSwatch(difficulty),
+                            ));
+                        }
+                    })
+                    //cycle button
+                    .with_children(|parent| {
+                        spawn_button(
+                            parent,
+                            &theme,
+                            &font,
+                            ButtonSpec::new(option_text(
+                                option,
+                                &accessibility,
+                                &localization,
+                                &save_data,
+                            ))
+                            .size(300.0, 50.0)
+                            .font_size(32.0),
+                        )
+                        .insert(option);
+                    });
+            }
+        })
+        //spawn "main menu" button
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                &font,
+                ButtonSpec::new(localization.text("main_menu"))
+                    .localized("main_menu")
+                    .open(AppState::MainMenu),
+            );
+        });
+}
+
+//translation key of the option's name
+fn option_label(option: AccessibilityOption) -> &'static str {
+    match option {
+        AccessibilityOption::Palette => "colors",
+        AccessibilityOption::Contrast => "crosshair",
+        AccessibilityOption::CrosshairSize => "crosshair_size",
+        AccessibilityOption::Flash => "flashes",
+        AccessibilityOption::Motion => "screen_shake",
+        AccessibilityOption::AimAssist => "aim_assist",
+        AccessibilityOption::Language => "language",
+    }
+}
+
+fn option_text(
+    option: AccessibilityOption,
+    accessibility: &Accessibility,
+    localization: &Localization,
+    save_data: &SaveData,
+) -> String {
+    match option {
+        AccessibilityOption::Palette => localization.text(accessibility.palette.text_key()),
+        AccessibilityOption::Contrast => {
+            if accessibility.high_contrast_crosshair {
+                localization.text("high_contrast")
+            } else {
+                localization.text("skin")
+            }
+        }
+        AccessibilityOption::CrosshairSize => {
+            format!("{:.0}%", accessibility.crosshair_scale * 100.0)
+        }
+        AccessibilityOption::Flash => {
+            if accessibility.reduced_flash {
+                localization.text("reduced")
+            } else {
+                localization.text("full")
+            }
+        }
+        AccessibilityOption::Motion => {
+            if save_data.reduce_motion {
+                localization.text("off")
+            } else {
+                localization.text("on")
+            }
+        }
+        AccessibilityOption::AimAssist => localization.text(accessibility.aim_assist.text_key()),
+        AccessibilityOption::Language => localization.name().to_string(),
+    }
+}
+
+pub fn accessibility_actions(
+    asset_server: Res<AssetServer>,
+    mut accessibility: ResMut<Accessibility>,
+    mut localization: ResMut<Localization>,
+    mut save_data: ResMut<SaveData>,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    buttons: Query<(&AccessibilityOption, &Children)>,
+    mut texts: Query<&mut Text>,
+    mut swatches: Query<(&PaletteSwatch, &mut BackgroundColor), Without<MenuButton>>,
+) {
+    for event in pressed_reader.iter() {
+        let Ok((&option, _)) = buttons.get(event.entity) else {
+            continue;
+        };
+
+        match option {
+            AccessibilityOption::Palette => {
+                accessibility.palette = accessibility.palette.next();
+
+                for (swatch, mut swatch_color) in &mut swatches {
+                    *swatch_color = accessibility.palette.difficulty_color(swatch.0).into();
+                }
+            }
+            AccessibilityOption::Contrast => {
+                accessibility.high_contrast_crosshair = !accessibility.high_contrast_crosshair;
+            }
+            AccessibilityOption::CrosshairSize => {
+                accessibility.crosshair_scale = accessibility.next_crosshair_scale();
+            }
+            AccessibilityOption::Flash => {
+                accessibility.reduced_flash = !accessibility.reduced_flash;
+            }
+            AccessibilityOption::Motion => {
+                save_data.reduce_motion = !save_data.reduce_motion;
+            }
+            AccessibilityOption::AimAssist => {
+                accessibility.aim_assist = accessibility.aim_assist.next();
+            }
+            AccessibilityOption::Language => {
+                save_data.language = save_data.language.next();
+                *localization = Localization::new(save_data.language);
+
+                //every value is in the old language, the names follow by themselves
+                for (&option, children) in &buttons {
+                    for &child in children.iter() {
+                        if let Ok(mut text) = texts.get_mut(child) {
+                            text.sections[0].value =
+                                option_text(option, &accessibility, &localization, &save_data);
+                            text.sections[0].style.font = localization.font(&asset_server);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Ok((_, children)) = buttons.get(event.entity) {
+            let text = option_text(option, &accessibility, &localization, &save_data);
+            set_button_label(children, &mut texts, text);
+        }
+    }
+}
+
+//store the settings once the player leaves the screen
+pub fn accessibility_store(accessibility: Res<Accessibility>, mut save_data: ResMut<SaveData>) {
+    save_data.accessibility = accessibility.clone();
+    save_data.store();
+}
+
+pub fn entity_despawner(
+    mut entities: Query<Entity, With<AccessibilityEntity>>,
+    mut commands: Commands,
+) {
+    info!("Accessibility Despawner Activated");
+
+    //despawn everyting in Accessibility
+    for entities_despawner in &mut entities {
+        commands.entity(entities_despawner).despawn_recursive();
+    }
+}
@@ -0,0 +1,209 @@
+use bevy::{core_pipeline::bloom::BloomSettings, prelude::*};
+use serde::{Deserialize, Serialize};
+
+mod accessibility_ui;
+
+use crate::ingame::spawn::Ball;
+use crate::ingame::versus::MainCamera;
+use crate::AppState;
+use crate::GameDifficultyState;
+use accessibility_ui::*;
+
+//bloom intensity of the main camera, bevy's default and the reduced flash one
+const BLOOM_INTENSITY: f32 = BloomSettings::NATURAL.intensity;
+const REDUCED_BLOOM_INTENSITY: f32 = 0.04;
+//balls are drawn brighter than their menu color so they still glow
+const BALL_GLOW: f32 = 1.8;
+//crosshair sizes to cycle through, relative to the skin's size
+pub const CROSSHAIR_SCALES: [f32; 4] = [0.75, 1.0, 1.5, 2.0];
+
+//difficulty colors, the default one plus palettes built from colors
+//that stay apart for the common kinds of color blindness
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Standard,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+    ];
+
+    //translation key of the palette's name
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            Palette::Standard => "palette_standard",
+            Palette::Deuteranopia => "palette_deuteranopia",
+            Palette::Protanopia => "palette_protanopia",
+            Palette::Tritanopia => "palette_tritanopia",
+        }
+    }
+
+    pub fn next(&self) -> Palette {
+        let index = Palette::ALL.iter().position(|palette| palette == self);
+        Palette::ALL[index.map_or(0, |index| (index + 1) % Palette::ALL.len())]
+    }
+
+    //color of the difficulty in menus
+    pub fn difficulty_color(&self, difficulty: GameDifficultyState) -> Color {
+        match (self, difficulty) {
+            (Palette::Standard, GameDifficultyState::Easy) => Color::rgb(0.19, 0.76, 0.41),
+            (Palette::Standard, GameDifficultyState::Medium) => Color::rgb(0.35, 0.67, 0.89),
+            (Palette::Standard, GameDifficultyState::Hard) => Color::rgb(0.88, 0.21, 0.20),
+            //blue, yellow and vermillion
+            (Palette::Deuteranopia, GameDifficultyState::Easy) => Color::rgb(0.0, 0.45, 0.70),
+            (Palette::Deuteranopia, GameDifficultyState::Medium) => Color::rgb(0.94, 0.89, 0.26),
+            (Palette::Deuteranopia, GameDifficultyState::Hard) => Color::rgb(0.84, 0.37, 0.0),
+            //reds look dark without red cones, hard gets a bright orange
+            (Palette::Protanopia, GameDifficultyState::Easy) => Color::rgb(0.34, 0.71, 0.91),
+            (Palette::Protanopia, GameDifficultyState::Medium) => Color::rgb(0.94, 0.89, 0.26),
+            (Palette::Protanopia, GameDifficultyState::Hard) => Color::rgb(0.90, 0.62, 0.0),
+            //teal, pink and red instead of blue against yellow
+            (Palette::Tritanopia, GameDifficultyState::Easy) => Color::rgb(0.0, 0.62, 0.62),
+            (Palette::Tritanopia, GameDifficultyState::Medium) => Color::rgb(0.95, 0.55, 0.65),
+            (Palette::Tritanopia, GameDifficultyState::Hard) => Color::rgb(0.85, 0.10, 0.10),
+        }
+    }
+
+    //tint of the ball, standard keeps the original glow
+    pub fn ball_color(&self, ball: Ball) -> Color {
+        let difficulty = match ball {
+            Ball::Easy => GameDifficultyState::Easy,
+            Ball::Medium => GameDifficultyState::Medium,
+            Ball::Hard => GameDifficultyState::Hard,
+        };
+
+        match self {
+            Palette::Standard => ball.color(),
+            _ => self.difficulty_color(difficulty) * BALL_GLOW,
+        }
+    }
+}
+
+//helps players who can't aim precisely, assisted runs keep their own records
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum AimAssist {
+    #[default]
+    Off,
+    Low,
+    Medium,
+    High,
+}
+
+impl AimAssist {
+    pub const ALL: [AimAssist; 4] = [
+        AimAssist::Off,
+        AimAssist::Low,
+        AimAssist::Medium,
+        AimAssist::High,
+    ];
+
+    //translation key of the level's name
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            AimAssist::Off => "assist_off",
+            AimAssist::Low => "assist_low",
+            AimAssist::Medium => "assist_medium",
+            AimAssist::High => "assist_high",
+        }
+    }
+
+    pub fn next(&self) -> AimAssist {
+        let index = AimAssist::ALL.iter().position(|level| level == self);
+        AimAssist::ALL[index.map_or(0, |index| (index + 1) % AimAssist::ALL.len())]
+    }
+
+    //radius of the crosshair's hit sensor
+    pub fn sensor_radius(&self) -> f32 {
+        match self {
+            AimAssist::Off => 5.0,
+            AimAssist::Low => 12.0,
+            AimAssist::Medium => 20.0,
+            AimAssist::High => 30.0,
+        }
+    }
+
+    //share of the way the crosshair is pulled toward a nearby ball
+    pub fn pull(&self) -> f32 {
+        match self {
+            AimAssist::Off => 0.0,
+            AimAssist::Low => 0.1,
+            AimAssist::Medium => 0.2,
+            AimAssist::High => 0.35,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    pub palette: Palette,
+    //bright crosshair with a dark outline, whatever the skin
+    pub high_contrast_crosshair: bool,
+    //tones down bloom, the muzzle flash and the impact sparks
+    pub reduced_flash: bool,
+    //only the sprite grows, the hit area stays the skin's so it's no assist
+    pub crosshair_scale: f32,
+    pub aim_assist: AimAssist,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Standard,
+            high_contrast_crosshair: false,
+            reduced_flash: false,
+            crosshair_scale: 1.0,
+            aim_assist: AimAssist::Off,
+        }
+    }
+}
+
+impl Accessibility {
+    pub fn next_crosshair_scale(&self) -> f32 {
+        let index = CROSSHAIR_SCALES
+            .iter()
+            .position(|scale| *scale == self.crosshair_scale);
+        CROSSHAIR_SCALES[index.map_or(1, |index| (index + 1) % CROSSHAIR_SCALES.len())]
+    }
+}
+
+pub fn bloom_apply(
+    accessibility: Res<Accessibility>,
+    mut bloom: Query<&mut BloomSettings, With<MainCamera>>,
+) {
+    for mut bloom in &mut bloom {
+        bloom.intensity = if accessibility.reduced_flash {
+            REDUCED_BLOOM_INTENSITY
+        } else {
+            BLOOM_INTENSITY
+        };
+    }
+}
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Accessibility), setup)
+            .add_systems(
+                Update,
+                accessibility_actions.run_if(in_state(AppState::Accessibility)),
+            )
+            .add_systems(
+                Update,
+                bloom_apply.run_if(resource_changed::<Accessibility>()),
+            )
+            .add_systems(
+                OnExit(AppState::Accessibility),
+                (entity_despawner, accessibility_store),
+            );
+    }
+}
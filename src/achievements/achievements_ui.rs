@@ -0,0 +1,205 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::achievements::Achievement;
+use crate::achievements::AchievementUnlockedEvent;
+use crate::localization::Localization;
+use crate::save::SaveData;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonSpec;
+use crate::widgets::UiTheme;
+use crate::AppState;
+
+#[derive(Component)]
+pub struct AchievementsEntity;
+
+#[derive(Component)]
+pub struct AchievementToast {
+    pub lifetime: Timer,
+}
+
+const LOCKED_TEXT: Color = Color::rgb(0.4, 0.4, 0.4);
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    theme: Res<UiTheme>,
+    save_data: Res<SaveData>,
+) {
+    info!("Achievements menu activated");
+
+    let font = localization.font(&asset_server);
+
+    //create full screen node bundle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(AchievementsEntity)
+        //create yellow title background
+        .with_children(|parent| {
+            parent
+                .spawn(title_panel(&theme, 800.0, 80.0))
+                //"achievements" text
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        localization.text("achievements"),
+                        &font,
+                        80.0,
+                        theme.title_text,
+                    ));
+                });
+        })
+        //create black gallery background
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(900.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(15.0)),
+                        row_gap: Val::Px(5.0),
+                        ..default()
+                    },
+                    background_color: Color::BLACK.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for achievement in Achievement::ALL {
+                        let unlocked = save_data.achievements.contains(&achievement);
+                        let (name_color, description_color) = if unlocked {
+                            (Color::YELLOW, Color::WHITE)
+                        } else {
+                            (LOCKED_TEXT, LOCKED_TEXT)
+                        };
+
+                        parent.spawn(TextBundle::from_sections([
+                            TextSection::new(
+                                format!("{}  ", achievement.name(&localization)),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 32.0,
+                                    color: name_color,
+                                },
+                            ),
+                            TextSection::new(
+                                achievement.description(&localization),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 26.0,
+                                    color: description_color,
+                                },
+                            ),
+                        ]));
+                    }
+                });
+        })
+        //spawn "main menu" button
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                &font,
+                ButtonSpec::new(localization.text("main_menu")).open(AppState::MainMenu),
+            );
+        });
+}
+
+pub fn entity_despawner(
+    mut entities: Query<Entity, With<AchievementsEntity>>,
+    mut commands: Commands,
+) {
+    info!("Achievements Despawner Activated");
+
+    //despawn everyting in Achievements
+    for entities_despawner in &mut entities {
+        commands.entity(entities_despawner).despawn_recursive();
+    }
+}
+
+//toasts aren't tied to a state, so unlocks at game over still get shown
+pub fn achievement_toast_spawn(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    audio: Res<Audio>,
+    mut unlock_event: EventReader<AchievementUnlockedEvent>,
+    toasts: Query<&AchievementToast>,
+) {
+    let mut toast_count = toasts.iter().count();
+    let font = localization.font(&asset_server);
+
+    for AchievementUnlockedEvent(achievement) in unlock_event.iter() {
+        audio.play(asset_server.load("sounds/click.ogg"));
+
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(20.0 + toast_count as f32 * 90.0),
+                    right: Val::Px(20.0),
+                    width: Val::Px(420.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(3.0)),
+                    ..default()
+                },
+                border_color: BorderColor(Color::YELLOW),
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            })
+            .insert(AchievementToast {
+                lifetime: Timer::from_seconds(3.0, TimerMode::Once),
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    localization
+                        .format("achievement_unlocked", &[&achievement.name(&localization)]),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color: Color::YELLOW,
+                    },
+                ));
+                parent.spawn(TextBundle::from_section(
+                    achievement.description(&localization),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+
+        toast_count += 1;
+    }
+}
+
+pub fn achievement_toast_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts: Query<(Entity, &mut AchievementToast)>,
+) {
+    for (toast_entity, mut toast) in &mut toasts {
+        toast.lifetime.tick(time.delta());
+
+        if toast.lifetime.finished() {
+            commands.entity(toast_entity).despawn_recursive();
+        }
+    }
+}
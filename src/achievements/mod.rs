@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+mod achievements_ui;
+mod tracker;
+
+use crate::ingame::run_stats::run_stats_recorder;
+use crate::localization::Localization;
+use crate::AppState;
+use achievements_ui::*;
+use tracker::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstTouch,
+    PerfectTen,
+    HardFifty,
+    Century,
+    Survivor,
+    Sharpshooter,
+    DailyRunner,
+    Veteran,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 8] = [
+        Achievement::FirstTouch,
+        Achievement::PerfectTen,
+        Achievement::HardFifty,
+        Achievement::Century,
+        Achievement::Survivor,
+        Achievement::Sharpshooter,
+        Achievement::DailyRunner,
+        Achievement::Veteran,
+    ];
+
+    //translation key of the name, the description key adds `_description`
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            Achievement::FirstTouch => "first_touch",
+            Achievement::PerfectTen => "perfect_ten",
+            Achievement::HardFifty => "hard_fifty",
+            Achievement::Century => "century",
+            Achievement::Survivor => "survivor",
+            Achievement::Sharpshooter => "sharpshooter",
+            Achievement::DailyRunner => "daily_runner",
+            Achievement::Veteran => "veteran",
+        }
+    }
+
+    pub fn name(&self, localization: &Localization) -> String {
+        localization.text(self.text_key())
+    }
+
+    pub fn description(&self, localization: &Localization) -> String {
+        localization.text(&format!("{}_description", self.text_key()))
+    }
+}
+
+#[derive(Event)]
+pub struct AchievementUnlockedEvent(pub Achievement);
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AchievementUnlockedEvent>()
+            .init_resource::<AchievementTracker>()
+            .add_systems(OnEnter(AppState::InGame), achievement_tracker_reset)
+            .add_systems(
+                Update,
+                achievement_tracker.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                OnExit(AppState::InGame),
+                achievement_game_over.after(run_stats_recorder),
+            )
+            .add_systems(Update, (achievement_toast_spawn, achievement_toast_fade))
+            .add_systems(OnEnter(AppState::Achievements), setup)
+            .add_systems(OnExit(AppState::Achievements), entity_despawner);
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::achievements::Achievement;
+use crate::achievements::AchievementUnlockedEvent;
+use crate::ingame::gameplay::ShotFiredEvent;
+use crate::ingame::rules::GameMode;
+use crate::ingame::rules::RunRules;
+use crate::ingame::run_stats::RunStats;
+use crate::save::SaveData;
+use crate::GameDifficultyState;
+
+//per run progress that isn't already part of RunStats
+#[derive(Resource, Default)]
+pub struct AchievementTracker {
+    pub hits_without_miss: u32,
+}
+
+pub fn achievement_tracker_reset(mut tracker: ResMut<AchievementTracker>) {
+    *tracker = AchievementTracker::default();
+}
+
+pub fn achievement_tracker(
+    run_stats: Res<RunStats>,
+    rules: Res<RunRules>,
+    difficulty: Res<State<GameDifficultyState>>,
+    mut tracker: ResMut<AchievementTracker>,
+    mut save_data: ResMut<SaveData>,
+    mut shot_event_reader: EventReader<ShotFiredEvent>,
+    mut unlock_event: EventWriter<AchievementUnlockedEvent>,
+) {
+    if !earns_achievements(&rules) {
+        return;
+    }
+
+    for shot in shot_event_reader.iter() {
+        if shot.hit {
+            tracker.hits_without_miss += 1;
+        } else {
+            tracker.hits_without_miss = 0;
+        }
+    }
+
+    let mut unlocked = Vec::new();
+
+    if run_stats.kick_ups >= 1 {
+        unlocked.push(Achievement::FirstTouch);
+    }
+    if tracker.hits_without_miss >= 10 {
+        unlocked.push(Achievement::PerfectTen);
+    }
+    if run_stats.kick_ups >= 50 && *difficulty.get() == GameDifficultyState::Hard {
+        unlocked.push(Achievement::HardFifty);
+    }
+    if run_stats.kick_ups >= 100 {
+        unlocked.push(Achievement::Century);
+    }
+    if run_stats.run_time >= 120.0 {
+        unlocked.push(Achievement::Survivor);
+    }
+
+    unlock(&mut save_data, &unlocked, &mut unlock_event);
+}
+
+//checks that need the finished run
+pub fn achievement_game_over(
+    run_stats: Res<RunStats>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    mut save_data: ResMut<SaveData>,
+    mut unlock_event: EventWriter<AchievementUnlockedEvent>,
+) {
+    if !earns_achievements(&rules) {
+        return;
+    }
+
+    let mut unlocked = Vec::new();
+
+    if run_stats.shots_fired >= 20 && run_stats.accuracy() >= 90.0 {
+        unlocked.push(Achievement::Sharpshooter);
+    }
+    if *mode == GameMode::Daily {
+        unlocked.push(Achievement::DailyRunner);
+    }
+    if save_data.lifetime_stats.games_played >= 50 {
+        unlocked.push(Achievement::Veteran);
+    }
+
+    unlock(&mut save_data, &unlocked, &mut unlock_event);
+}
+
+//practice, assisted and unranked runs make the goals too easy
+fn earns_achievements(rules: &RunRules) -> bool {
+    rules.scored && !rules.assisted()
+}
+
+fn unlock(
+    save_data: &mut SaveData,
+    achievements: &[Achievement],
+    unlock_event: &mut EventWriter<AchievementUnlockedEvent>,
+) {
+    let mut changed = false;
+
+    for achievement in achievements {
+        if !save_data.achievements.contains(achievement) {
+            info!("Achievement unlocked: {:?}", achievement);
+            save_data.achievements.push(*achievement);
+            unlock_event.send(AchievementUnlockedEvent(*achievement));
+            changed = true;
+        }
+    }
+
+    if changed {
+        save_data.store();
+    }
+}
@@ -0,0 +1,357 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+
+use crate::cosmetics::Loadout;
+use crate::cosmetics::BALL_SKINS;
+use crate::cosmetics::CROSSHAIRS;
+use crate::cosmetics::WEAPON_CAMOS;
+use crate::localization::Localization;
+use crate::save::SaveData;
+use crate::widgets::set_button_label;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonPressedEvent;
+use crate::widgets::ButtonSpec;
+use crate::widgets::MenuButton;
+use crate::widgets::UiTheme;
+use crate::AppState;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum CosmeticSlot {
+    Ball,
+    Crosshair,
+    Camo,
+}
+
+#[derive(Component)]
+pub struct CosmeticPreview(pub CosmeticSlot);
+
+#[derive(Component)]
+pub struct CustomizeEntity;
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    theme: Res<UiTheme>,
+    mut textures: ResMut<Assets<TextureAtlas>>,
+    loadout: Res<Loadout>,
+    save_data: Res<SaveData>,
+) {
+    info!("Customize menu activated");
+
+    let font = localization.font(&asset_server);
+
+    let m4_atlas = textures.add(TextureAtlas::from_grid(
+        asset_server.load("sprites/m4_sheet.png"),
+        Vec2::new(1550.0, 720.0),
+        5,
+        1,
+        None,
+        None,
+    ));
+
+    //create full screen node bundle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(25.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(CustomizeEntity)
+        //create yellow title background
+        .with_children(|parent| {
+            parent
+                .spawn(title_panel(&theme, 800.0, 100.0))
+                //"customize" text
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        localization.text("customize"),
+                        &font,
+                        100.0,
+                        theme.title_text,
+                    ));
+                });
+        })
+        //one row per slot: preview and a button cycling through unlocked items
+        .with_children(|parent| {
+            for slot in [
+                CosmeticSlot::Ball,
+                CosmeticSlot::Crosshair,
+                CosmeticSlot::Camo,
+            ] {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(800.0),
+                            height: Val::Px(100.0),
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::SpaceBetween,
+                            padding: UiRect::horizontal(Val::Px(20.0)),
+                            ..default()
+                        },
+                        background_color: Color::BLACK.into(),
+                        ..default()
+                    })
+                    //preview
+                    .with_children(|parent| {
+                        let preview_color = preview_color(slot, &loadout);
+
+                        match slot {
+                            CosmeticSlot::Ball => {
+                                parent.spawn((
+                                    ImageBundle {
+                                        style: Style {
+                                            width: Val::Px(80.0),
+                                            height: Val::Px(80.0),
+                                            ..default()
+                                        },
+                                        image: UiImage::new(
+                                            asset_server.load(
+                                                loadout
+                                                    .ball_skin()
+                                                    .sprite
+                                                    .unwrap_or("sprites/medium_ball.png"),
+                                            ),
+                                        ),
+                                        background_color: preview_color.into(),
+                                        ..default()
+                                    },
+                                    CosmeticPreview(slot),
+                                ));
+                            }
+                            CosmeticSlot::Crosshair => {
+                                parent.spawn((
+                                    ImageBundle {
+                                        style: Style {
+                                            width: Val::Px(loadout.crosshair().size * 4.0),
+                                            height: Val::Px(loadout.crosshair().size * 4.0),
+                                            ..default()
+                                        },
+                                        image: UiImage::new(
+                                            asset_server.load("sprites/crosshair.png"),
+                                        ),
+                                        background_color: preview_color.into(),
+                                        ..default()
+                                    },
+                                    CosmeticPreview(slot),
+                                ));
+                            }
+                            CosmeticSlot::Camo => {
+                                parent.spawn((
+                                    AtlasImageBundle {
+                                        style: Style {
+                                            width: Val::Px(186.0),
+                                            height: Val::Px(86.0),
+                                            ..default()
+                                        },
+                                        texture_atlas: m4_atlas.clone(),
+                                        background_color: preview_color.into(),
+                                        ..default()
+                                    },
+                                    CosmeticPreview(slot),
+                                ));
+                            }
+                        }
+                    })
+                    //cycle button
+                    .with_children(|parent| {
+                        spawn_button(
+                            parent,
+                            &theme,
+                            &font,
+                            ButtonSpec::new(slot_text(slot, &loadout, &save_data, &localization))
+                                .size(460.0, 65.0)
+                                .font_size(32.0),
+                        )
+                        .insert(slot);
+                    });
+            }
+        })
+        //spawn "main menu" button
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                &font,
+                ButtonSpec::new(localization.text("main_menu")).open(AppState::MainMenu),
+            );
+        });
+}
+
+fn preview_color(slot: CosmeticSlot, loadout: &Loadout) -> Color {
+    match slot {
+        CosmeticSlot::Ball => loadout
+            .ball_skin()
+            .tint
+            .unwrap_or(Color::rgb(0.7, 1.4, 1.8)),
+        CosmeticSlot::Crosshair => loadout.crosshair().color,
+        CosmeticSlot::Camo => loadout.camo().tint,
+    }
+}
+
+fn slot_text(
+    slot: CosmeticSlot,
+    loadout: &Loadout,
+    save_data: &SaveData,
+    localization: &Localization,
+) -> String {
+    let (key, name_key, unlocked, total) = match slot {
+        CosmeticSlot::Ball => (
+            "ball_slot",
+            loadout.ball_skin().text_key,
+            BALL_SKINS
+                .iter()
+                .filter(|skin| skin.unlock.is_unlocked(save_data))
+                .count(),
+            BALL_SKINS.len(),
+        ),
+        CosmeticSlot::Crosshair => (
+            "crosshair_slot",
+            loadout.crosshair().text_key,
+            CROSSHAIRS
+                .iter()
+                .filter(|skin| skin.unlock.is_unlocked(save_data))
+                .count(),
+            CROSSHAIRS.len(),
+        ),
+        CosmeticSlot::Camo => (
+            "camo_slot",
+            loadout.camo().text_key,
+            WEAPON_CAMOS
+                .iter()
+                .filter(|camo| camo.unlock.is_unlocked(save_data))
+                .count(),
+            WEAPON_CAMOS.len(),
+        ),
+    };
+
+    localization.format(key, &[&localization.text(name_key), &unlocked, &total])
+}
+
+//pick the next unlocked item after `current`, wrapping around
+fn next_unlocked<'a>(
+    ids: impl Iterator<Item = (&'a str, bool)> + Clone,
+    current: &str,
+) -> Option<&'a str> {
+    let unlocked: Vec<&str> = ids
+        .filter(|(_, unlocked)| *unlocked)
+        .map(|(id, _)| id)
+        .collect();
+    let index = unlocked.iter().position(|id| *id == current)?;
+
+    unlocked.get((index + 1) % unlocked.len()).copied()
+}
+
+pub fn cosmetic_actions(
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+    mut loadout: ResMut<Loadout>,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    buttons: Query<(&CosmeticSlot, &Children)>,
+    mut texts: Query<&mut Text>,
+    mut previews: Query<
+        (
+            &CosmeticPreview,
+            &mut BackgroundColor,
+            Option<&mut UiImage>,
+            &mut Style,
+        ),
+        Without<MenuButton>,
+    >,
+) {
+    for event in pressed_reader.iter() {
+        let Ok((&slot, children)) = buttons.get(event.entity) else {
+            continue;
+        };
+
+        match slot {
+            CosmeticSlot::Ball => {
+                let ids = BALL_SKINS
+                    .iter()
+                    .map(|skin| (skin.id, skin.unlock.is_unlocked(&save_data)));
+                if let Some(id) = next_unlocked(ids, loadout.ball_skin().id) {
+                    loadout.ball_skin = id.to_string();
+                }
+            }
+            CosmeticSlot::Crosshair => {
+                let ids = CROSSHAIRS
+                    .iter()
+                    .map(|skin| (skin.id, skin.unlock.is_unlocked(&save_data)));
+                if let Some(id) = next_unlocked(ids, loadout.crosshair().id) {
+                    loadout.crosshair = id.to_string();
+                }
+            }
+            CosmeticSlot::Camo => {
+                let ids = WEAPON_CAMOS
+                    .iter()
+                    .map(|camo| (camo.id, camo.unlock.is_unlocked(&save_data)));
+                if let Some(id) = next_unlocked(ids, loadout.camo().id) {
+                    loadout.camo = id.to_string();
+                }
+            }
+        }
+
+        set_button_label(
+            children,
+            &mut texts,
+            slot_text(slot, &loadout, &save_data, &localization),
+        );
+
+        //refresh the preview of this slot
+        for (preview, mut preview_color, image, mut style) in &mut previews {
+            if preview.0 != slot {
+                continue;
+            }
+
+            *preview_color = self::preview_color(slot, &loadout).into();
+
+            match slot {
+                CosmeticSlot::Ball => {
+                    if let Some(mut image) = image {
+                        image.texture = asset_server.load(
+                            loadout
+                                .ball_skin()
+                                .sprite
+                                .unwrap_or("sprites/medium_ball.png"),
+                        );
+                    }
+                }
+                CosmeticSlot::Crosshair => {
+                    style.width = Val::Px(loadout.crosshair().size * 4.0);
+                    style.height = Val::Px(loadout.crosshair().size * 4.0);
+                }
+                CosmeticSlot::Camo => {}
+            }
+        }
+    }
+}
+
+//store the loadout once the player leaves the screen
+pub fn loadout_store(loadout: Res<Loadout>, mut save_data: ResMut<SaveData>) {
+    save_data.loadout = loadout.clone();
+    save_data.store();
+}
+
+pub fn entity_despawner(
+    mut entities: Query<Entity, With<CustomizeEntity>>,
+    mut commands: Commands,
+) {
+    info!("Customize Despawner Activated");
+
+    //despawn everyting in Customize
+    for entities_despawner in &mut entities {
+        commands.entity(entities_despawner).despawn_recursive();
+    }
+}
@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+mod cosmetics_ui;
+
+use crate::achievements::Achievement;
+use crate::save::SaveData;
+use crate::AppState;
+use cosmetics_ui::*;
+
+pub enum Unlock {
+    Free,
+    //best score of any run, on any difficulty
+    Score(i32),
+    Achievement(Achievement),
+}
+
+impl Unlock {
+    pub fn is_unlocked(&self, save_data: &SaveData) -> bool {
+        match self {
+            Unlock::Free => true,
+            Unlock::Score(score) => save_data.lifetime_stats.best_score >= *score,
+            Unlock::Achievement(achievement) => save_data.achievements.contains(achievement),
+        }
+    }
+}
+
+pub struct BallSkin {
+    pub id: &'static str,
+    //translation key of the shown name
+    pub text_key: &'static str,
+    //none keeps the difficulty look
+    pub sprite: Option<&'static str>,
+    pub tint: Option<Color>,
+    pub unlock: Unlock,
+}
+
+pub struct CrosshairSkin {
+    pub id: &'static str,
+    //translation key of the shown name
+    pub text_key: &'static str,
+    pub color: Color,
+    pub size: f32,
+    pub unlock: Unlock,
+}
+
+pub struct WeaponCamo {
+    pub id: &'static str,
+    //translation key of the shown name
+    pub text_key: &'static str,
+    pub tint: Color,
+    pub unlock: Unlock,
+}
+
+pub const BALL_SKINS: [BallSkin; 5] = [
+    BallSkin {
+        id: "classic",
+        text_key: "skin_classic",
+        sprite: None,
+        tint: None,
+        unlock: Unlock::Free,
+    },
+    BallSkin {
+        id: "mint",
+        text_key: "skin_mint",
+        sprite: Some("sprites/easy_ball.png"),
+        tint: Some(Color::rgb(0.6, 1.8, 1.2)),
+        unlock: Unlock::Score(15),
+    },
+    BallSkin {
+        id: "ember",
+        text_key: "skin_ember",
+        sprite: Some("sprites/hard_ball.png"),
+        tint: Some(Color::rgb(2.2, 0.9, 0.2)),
+        unlock: Unlock::Score(40),
+    },
+    BallSkin {
+        id: "frost",
+        text_key: "skin_frost",
+        sprite: Some("sprites/medium_ball.png"),
+        tint: Some(Color::rgb(1.2, 1.8, 2.4)),
+        unlock: Unlock::Achievement(Achievement::PerfectTen),
+    },
+    BallSkin {
+        id: "gold",
+        text_key: "skin_gold",
+        sprite: None,
+        tint: Some(Color::rgb(2.5, 2.0, 0.4)),
+        unlock: Unlock::Achievement(Achievement::Century),
+    },
+];
+
+pub const CROSSHAIRS: [CrosshairSkin; 4] = [
+    CrosshairSkin {
+        id: "classic",
+        text_key: "crosshair_classic",
+        color: Color::rgb(1.0, 1.0, 1.0),
+        size: 10.0,
+        unlock: Unlock::Free,
+    },
+    CrosshairSkin {
+        id: "green",
+        text_key: "crosshair_green",
+        color: Color::rgb(0.2, 1.5, 0.4),
+        size: 10.0,
+        unlock: Unlock::Score(10),
+    },
+    CrosshairSkin {
+        id: "red_dot",
+        text_key: "crosshair_red_dot",
+        color: Color::rgb(2.0, 0.1, 0.1),
+        size: 7.0,
+        unlock: Unlock::Score(30),
+    },
+    CrosshairSkin {
+        id: "big_yellow",
+        text_key: "crosshair_big_yellow",
+        color: Color::rgb(2.0, 2.0, 0.2),
+        size: 16.0,
+        unlock: Unlock::Achievement(Achievement::Sharpshooter),
+    },
+];
+
+pub const WEAPON_CAMOS: [WeaponCamo; 5] = [
+    WeaponCamo {
+        id: "factory",
+        text_key: "camo_factory",
+        tint: Color::rgb(1.0, 1.0, 1.0),
+        unlock: Unlock::Free,
+    },
+    WeaponCamo {
+        id: "desert",
+        text_key: "camo_desert",
+        tint: Color::rgb(1.0, 0.85, 0.6),
+        unlock: Unlock::Score(20),
+    },
+    WeaponCamo {
+        id: "woodland",
+        text_key: "camo_woodland",
+        tint: Color::rgb(0.6, 0.85, 0.5),
+        unlock: Unlock::Achievement(Achievement::Survivor),
+    },
+    WeaponCamo {
+        id: "arctic",
+        text_key: "camo_arctic",
+        tint: Color::rgb(0.85, 0.95, 1.2),
+        unlock: Unlock::Achievement(Achievement::DailyRunner),
+    },
+    WeaponCamo {
+        id: "gold",
+        text_key: "camo_gold",
+        tint: Color::rgb(2.0, 1.6, 0.4),
+        unlock: Unlock::Achievement(Achievement::HardFifty),
+    },
+];
+
+//selected cosmetics, stored by id so reordering the registry keeps saves valid
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Loadout {
+    pub ball_skin: String,
+    pub crosshair: String,
+    pub camo: String,
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self {
+            ball_skin: BALL_SKINS[0].id.to_string(),
+            crosshair: CROSSHAIRS[0].id.to_string(),
+            camo: WEAPON_CAMOS[0].id.to_string(),
+        }
+    }
+}
+
+impl Loadout {
+    pub fn ball_skin(&self) -> &'static BallSkin {
+        BALL_SKINS
+            .iter()
+            .find(|skin| skin.id == self.ball_skin)
+            .unwrap_or(&BALL_SKINS[0])
+    }
+
+    pub fn crosshair(&self) -> &'static CrosshairSkin {
+        CROSSHAIRS
+            .iter()
+            .find(|skin| skin.id == self.crosshair)
+            .unwrap_or(&CROSSHAIRS[0])
+    }
+
+    pub fn camo(&self) -> &'static WeaponCamo {
+        WEAPON_CAMOS
+            .iter()
+            .find(|camo| camo.id == self.camo)
+            .unwrap_or(&WEAPON_CAMOS[0])
+    }
+}
+
+pub struct CosmeticsPlugin;
+
+impl Plugin for CosmeticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Customize), setup)
+            .add_systems(
+                Update,
+                cosmetic_actions.run_if(in_state(AppState::Customize)),
+            )
+            .add_systems(
+                OnExit(AppState::Customize),
+                (entity_despawner, loadout_store),
+            );
+    }
+}
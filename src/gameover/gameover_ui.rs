@@ -5,28 +5,139 @@ use bevy_kira_audio::prelude::*;
 
 use crate::AppState;
 
+use crate::ingame::rules::{GameMode, RunRules};
+use crate::ingame::run_stats::{format_time, RunStats};
+use crate::ingame::versus::{Player, Versus};
 use crate::ingame::Scores;
+use crate::lan::LanOpponent;
+use crate::localization::Localization;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonPressedEvent;
+use crate::widgets::ButtonSpec;
+use crate::widgets::UiTheme;
 
 #[derive(Component)]
-pub struct HomeButton;
+pub struct GameOverEntity;
 
 #[derive(Component)]
 pub struct RestartButton;
 
 #[derive(Component)]
-pub struct GameOverEntity;
+pub struct TitleText;
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+#[derive(Component)]
+pub struct HighScoreText;
 
 pub fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     audio: Res<Audio>,
+    theme: Res<UiTheme>,
     scores: Res<Scores>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    run_stats: Res<RunStats>,
+    versus: Res<Versus>,
+    opponent: Res<LanOpponent>,
+    localization: Res<Localization>,
 ) {
     info!("GameOver menu activated");
 
+    let font = localization.font(&asset_server);
+
+    let (title, title_size, score_text, high_score_text, restart_label) = match *mode {
+        GameMode::Standard | GameMode::Practice => (
+            localization.text("game_over"),
+            120.0,
+            localization.format("score", &[&scores.current_score]),
+            if rules.trajectory_preview {
+                localization.text("unranked_arc")
+            } else if rules.assisted() {
+                localization.format("assisted_best", &[&scores.high_score])
+            } else {
+                localization.format("high_score", &[&scores.high_score])
+            },
+            localization.text("restart"),
+        ),
+        GameMode::Daily => (
+            localization.text("game_over"),
+            120.0,
+            localization.format("score", &[&scores.current_score]),
+            localization.format("daily_score", &[&scores.high_score]),
+            localization.text("restart"),
+        ),
+        GameMode::Coop => (
+            localization.text("game_over"),
+            120.0,
+            localization.format("team_score", &[&scores.current_score]),
+            coop_mvp_text(&run_stats, &localization),
+            localization.text("restart"),
+        ),
+        GameMode::Lan => (
+            lan_title(&opponent, scores.current_score, &localization),
+            90.0,
+            localization.format("score", &[&scores.current_score]),
+            lan_opponent_text(&opponent, &localization),
+            localization.text("lobby"),
+        ),
+        //versus compares the two players instead of high scores
+        GameMode::HotSeat | GameMode::SplitScreen => {
+            let title = if versus.finished() {
+                match versus.winner() {
+                    Some(winner) => {
+                        localization.format("player_wins", &[&localization.text(winner.text_key())])
+                    }
+                    None => localization.text("draw"),
+                }
+            } else {
+                localization.text("game_over")
+            };
+            let restart_label = if versus.finished() {
+                localization.text("rematch")
+            } else {
+                localization.text(Player::Two.text_key())
+            };
+
+            (
+                title,
+                90.0,
+                versus_score_text(&versus, Player::One, &localization),
+                versus_score_text(&versus, Player::Two, &localization),
+                restart_label,
+            )
+        }
+    };
+
+    //co-op shows what each player contributed instead of the totals
+    let summary_text = if *mode == GameMode::Coop {
+        Player::ALL
+            .iter()
+            .map(|player| {
+                localization.format(
+                    "player_summary",
+                    &[
+                        &localization.text(player.text_key()),
+                        &run_stats.player_hits[player.index()],
+                        &format!("{:.0}", run_stats.player_accuracy(*player)),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("   ")
+    } else {
+        localization.format(
+            "run_summary",
+            &[
+                &run_stats.shots_fired,
+                &run_stats.hits,
+                &format!("{:.0}", run_stats.accuracy()),
+                &format_time(run_stats.run_time),
+            ],
+        )
+    };
+
     audio.play(asset_server.load("sounds/gameover_sound.ogg"));
 
     //create full screen node bundle
@@ -60,32 +171,42 @@ pub fn setup(
                     ..default()
                 })
                 //create yellow background
+                .with_children(|parent| {
+                    parent
+                        .spawn(title_panel(&theme, 800.0, 100.0))
+                        //"game over" text
+                        .with_children(|parent| {
+                            parent
+                                .spawn(text(title, &font, title_size, theme.title_text))
+                                .insert(TitleText);
+                        });
+                })
+                //current score: create black background
                 .with_children(|parent| {
                     parent
                         .spawn(NodeBundle {
                             style: Style {
-                                width: Val::Px(800.0),
-                                height: Val::Px(100.0),
+                                width: Val::Px(550.0),
+                                height: Val::Px(75.0),
                                 align_items: AlignItems::Center,
                                 justify_content: JustifyContent::Center,
                                 ..default()
                             },
-                            background_color: Color::YELLOW.into(),
+                            background_color: Color::BLACK.into(),
                             ..default()
                         })
-                        //"game over" text
                         .with_children(|parent| {
                             parent.spawn(TextBundle::from_section(
-                                "GAME OVER",
+                                score_text,
                                 TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 120.0,
-                                    color: Color::BLACK,
+                                    font: font.clone(),
+                                    font_size: 70.0,
+                                    color: Color::WHITE,
                                 },
                             ));
                         });
                 })
-                //current score: create black background
+                //high score: create black background
                 .with_children(|parent| {
                     parent
                         .spawn(NodeBundle {
@@ -100,23 +221,25 @@ pub fn setup(
                             ..default()
                         })
                         .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                format!("SCORE: {}", scores.current_score),
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 70.0,
-                                    color: Color::WHITE,
-                                },
-                            ));
+                            parent
+                                .spawn(TextBundle::from_section(
+                                    high_score_text,
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 70.0,
+                                        color: Color::WHITE,
+                                    },
+                                ))
+                                .insert(HighScoreText);
                         });
                 })
-                //high score: create black background
+                //run summary: create black background
                 .with_children(|parent| {
                     parent
                         .spawn(NodeBundle {
                             style: Style {
-                                width: Val::Px(550.0),
-                                height: Val::Px(75.0),
+                                width: Val::Px(800.0),
+                                height: Val::Px(50.0),
                                 align_items: AlignItems::Center,
                                 justify_content: JustifyContent::Center,
                                 ..default()
@@ -126,11 +249,11 @@ pub fn setup(
                         })
                         .with_children(|parent| {
                             parent.spawn(TextBundle::from_section(
-                                format!("HIGH SCORE: {}", scores.high_score),
+                                summary_text,
                                 TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 70.0,
-                                    color: Color::WHITE,
+                                    font: font.clone(),
+                                    font_size: 32.0,
+                                    color: Color::rgb(0.9, 0.9, 0.9),
                                 },
                             ));
                         });
@@ -151,118 +274,107 @@ pub fn setup(
                     },
                     ..default()
                 })
-                //spawn "main menu" button
+                //spawn "main menu" and restart buttons
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                width: Val::Px(200.0),
-                                height: Val::Px(65.0),
-                                border: UiRect::all(Val::Px(5.0)),
-                                // horizontally center child text
-                                justify_content: JustifyContent::Center,
-                                // vertically center child text
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            border_color: BorderColor(Color::BLACK),
-                            background_color: NORMAL_BUTTON.into(),
-                            ..default()
-                        })
-                        .insert(HomeButton)
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                "MAIN MENU",
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.9, 0.9, 0.9),
-                                },
-                            ));
-                        });
-
-                    //spawn restart button
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                width: Val::Px(200.0),
-                                height: Val::Px(65.0),
-                                border: UiRect::all(Val::Px(5.0)),
-                                // horizontally center child text
-                                justify_content: JustifyContent::Center,
-                                // vertically center child text
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            border_color: BorderColor(Color::BLACK),
-                            background_color: NORMAL_BUTTON.into(),
-                            ..default()
-                        })
-                        .insert(RestartButton)
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                "RESTART",
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.9, 0.9, 0.9),
-                                },
-                            ));
-                        });
+                    spawn_button(
+                        parent,
+                        &theme,
+                        &font,
+                        ButtonSpec::new(localization.text("main_menu"))
+                            .open(AppState::MainMenu)
+                            .shortcut(KeyCode::M),
+                    );
+                    spawn_button(
+                        parent,
+                        &theme,
+                        &font,
+                        ButtonSpec::new(restart_label).shortcut(KeyCode::R),
+                    )
+                    .insert(RestartButton);
                 });
         });
 }
 
-pub fn home_button_system(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<HomeButton>),
-    >,
+//the race is only decided once the opponent is out too, leaving mid-run forfeits it
+fn lan_title(opponent: &LanOpponent, score: i32, localization: &Localization) -> String {
+    match opponent.snapshot {
+        Some(snapshot) if !snapshot.alive => match score.cmp(&snapshot.score) {
+            std::cmp::Ordering::Greater => localization.text("you_win"),
+            std::cmp::Ordering::Less => localization.text("you_lose"),
+            std::cmp::Ordering::Equal => localization.text("draw"),
+        },
+        _ if opponent.left => localization.text("you_win"),
+        _ => localization.text("game_over"),
+    }
+}
+
+fn lan_opponent_text(opponent: &LanOpponent, localization: &Localization) -> String {
+    match opponent.snapshot {
+        //a finished opponent going back to the menu still keeps their result
+        Some(snapshot) if !snapshot.alive => localization.format("opponent", &[&snapshot.score]),
+        _ if opponent.left => localization.text("opponent_left"),
+        Some(snapshot) => localization.format("opponent_alive", &[&snapshot.score]),
+        None => localization.format("opponent", &[&0]),
+    }
+}
+
+//the opponent's final snapshot or departure can arrive after we're already out
+pub fn lan_result_update(
+    localization: Res<Localization>,
+    scores: Res<Scores>,
+    opponent: Res<LanOpponent>,
+    mut titles: Query<&mut Text, (With<TitleText>, Without<HighScoreText>)>,
+    mut opponent_texts: Query<&mut Text, (With<HighScoreText>, Without<TitleText>)>,
 ) {
-    for (interaction, mut color, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.insert_resource(NextState(Some(AppState::MainMenu)));
-            }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-                audio.play(asset_server.load("sounds/hover_button.ogg"));
-            }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
-            }
+    for mut text in &mut titles {
+        text.sections[0].value = lan_title(&opponent, scores.current_score, &localization);
+    }
+
+    for mut text in &mut opponent_texts {
+        text.sections[0].value = lan_opponent_text(&opponent, &localization);
+    }
+}
+
+//most hits in the run, not most points
+fn coop_mvp_text(run_stats: &RunStats, localization: &Localization) -> String {
+    let [one, two] = run_stats.player_hits;
+
+    match one.cmp(&two) {
+        std::cmp::Ordering::Greater => {
+            localization.format("mvp", &[&localization.text(Player::One.text_key())])
+        }
+        std::cmp::Ordering::Less => {
+            localization.format("mvp", &[&localization.text(Player::Two.text_key())])
         }
+        std::cmp::Ordering::Equal => localization.text("mvp_tied"),
     }
 }
 
-pub fn restart_button_system(
+fn versus_score_text(versus: &Versus, player: Player, localization: &Localization) -> String {
+    let name = localization.text(player.text_key());
+
+    match versus.scores[player.index()] {
+        Some(score) => localization.format("player_score", &[&name, &score]),
+        None => localization.format("player_score_none", &[&name]),
+    }
+}
+
+pub fn game_over_actions(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<RestartButton>),
-    >,
+    mode: Res<GameMode>,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    restart_buttons: Query<(), With<RestartButton>>,
 ) {
-    for (interaction, mut color, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.insert_resource(NextState(Some(AppState::InGame)));
-            }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-                audio.play(asset_server.load("sounds/hover_button.ogg"));
-            }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
-            }
+    for event in pressed_reader.iter() {
+        if !restart_buttons.contains(event.entity) {
+            continue;
+        }
+
+        //lan matches have to be started by the relay again
+        if *mode == GameMode::Lan {
+            commands.insert_resource(NextState(Some(AppState::Lobby)));
+        } else {
+            commands.insert_resource(NextState(Some(AppState::InGame)));
         }
     }
 }
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 mod gameover_ui;
 
+use crate::ingame::rules::GameMode;
+use crate::lan::LanOpponent;
 use crate::AppState;
 use gameover_ui::*;
 
@@ -12,7 +14,14 @@ impl Plugin for GameOverPlugin {
         app.add_systems(OnEnter(AppState::GameOver), setup)
             .add_systems(
                 Update,
-                (home_button_system, restart_button_system).run_if(in_state(AppState::GameOver)),
+                game_over_actions.run_if(in_state(AppState::GameOver)),
+            )
+            .add_systems(
+                Update,
+                lan_result_update
+                    .run_if(in_state(AppState::GameOver))
+                    .run_if(resource_equals(GameMode::Lan))
+                    .run_if(resource_changed::<LanOpponent>()),
             )
             .add_systems(OnExit(AppState::GameOver), entity_despawner);
     }
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::ingame::Ball;
+use crate::ingame::CursorCrosshair;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+
+//balls further than this past their edge don't pull the crosshair
+const PULL_RANGE: f32 = 120.0;
+
+//how far the assist moved the crosshair off the player's aim this frame
+#[derive(Component, Default)]
+pub struct AimPull(pub Vec2);
+
+//puts the crosshair back on the player's own aim before the inputs move it,
+//so the pull never builds up while the mouse or stick is idle
+pub fn aim_assist_release(mut crosshairs: Query<(&mut Transform, &mut AimPull)>) {
+    for (mut crosshair, mut aim_pull) in &mut crosshairs {
+        crosshair.translation -= aim_pull.0.extend(0.0);
+        aim_pull.0 = Vec2::ZERO;
+    }
+}
+
+//drags the crosshair part of the way toward the closest ball, stronger up close
+pub fn aim_assist(
+    rules: Res<RunRules>,
+    balls: Query<(&Transform, &Ball, &Player), Without<CursorCrosshair>>,
+    mut crosshairs: Query<(&mut Transform, &mut AimPull, &Player), With<CursorCrosshair>>,
+) {
+    let pull = rules.aim_assist.pull();
+    if pull == 0.0 {
+        return;
+    }
+
+    for (mut crosshair, mut aim_pull, player) in &mut crosshairs {
+        let position = crosshair.translation.truncate();
+
+        //co-op crosshairs share the ball, split-screen ones aim at their own
+        let closest = balls
+            .iter()
+            .filter(|(_, _, ball_player)| !rules.split_screen || *ball_player == player)
+            .map(|(transform, ball, _)| {
+                let offset = transform.translation.truncate() - position;
+                let range = ball.radius() * transform.scale.x + PULL_RANGE;
+                (offset, range)
+            })
+            .filter(|(offset, range)| offset.length() < *range)
+            .min_by(|(a, _), (b, _)| a.length().total_cmp(&b.length()));
+
+        let Some((offset, range)) = closest else {
+            continue;
+        };
+
+        let falloff = 1.0 - offset.length() / range;
+        aim_pull.0 = offset * pull * falloff;
+        crosshair.translation += aim_pull.0.extend(0.0);
+    }
+}
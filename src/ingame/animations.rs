@@ -1,29 +1,61 @@
+use std::{cmp::Reverse, time::Duration};
+
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
-use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::{prelude::*, rapier::geometry::CollisionEventFlags};
 use rand::Rng;
 
-use crate::ingame::Animation;
-use crate::ingame::AnimationState;
+use crate::accessibility::Accessibility;
+use crate::ingame::BulletTime;
 use crate::ingame::ContactAnimationEvent;
 use crate::ingame::CursorCrosshair;
 use crate::ingame::InGameEntity;
 use crate::ingame::M4AnimationEvent;
+use crate::ingame::PlaybackMode;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::SpriteAnimator;
+use crate::ingame::CASING_GROUP;
 use crate::ingame::M4;
+use crate::SCREEN_HEIGHT;
+use crate::SCREEN_WIDTH;
+
+//effects may fly this far past the edge of the screen before they're removed
+const EFFECT_BOUNDS_MARGIN: f32 = 200.0;
+
+//the fire sheet glows through bloom, reduced flash keeps it at plain yellow
+const FIRE_FLASH_COLOR: Color = Color::rgb(5.0, 5.0, 0.0);
+const REDUCED_FIRE_FLASH_COLOR: Color = Color::rgba(1.0, 1.0, 0.0, 0.5);
+
+//casings lie around this long, fading out at the end
+const CASING_LIFETIME: f32 = 6.0;
+const CASING_FADE: f32 = 1.0;
+//older casings start fading once the pile grows past this
+const MAX_CASINGS: usize = 30;
+const CASING_GRAVITY_SCALE: f32 = 200.0;
+//impacts slower than this are silent, full volume from the loud speed on
+const CASING_QUIET_SPEED: f32 = 80.0;
+const CASING_LOUD_SPEED: f32 = 600.0;
 
 #[derive(Component)]
 pub struct BulletCase {
     lifetime: Timer,
 }
 
+//spawned effects clean themselves up, and always go away with the game
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum EffectLifecycle {
+    UntilAnimationEnds,
+    UntilOffScreen,
+}
+
 pub fn contact_spawn(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut textures: ResMut<Assets<TextureAtlas>>,
     mut contact_event_reader: EventReader<ContactAnimationEvent>,
-    cursor_pos: Query<&Transform, With<CursorCrosshair>>,
 ) {
-    for _event in contact_event_reader.iter() {
+    for event in contact_event_reader.iter() {
         commands
             // Spawn a bevy sprite-sheet
             .spawn(SpriteSheetBundle {
@@ -35,32 +67,15 @@ pub fn contact_spawn(
                     None,
                     None,
                 )),
-                transform: Transform::from_xyz(
-                    cursor_pos.single().translation.x,
-                    cursor_pos.single().translation.y,
-                    -2.0,
-                ),
+                transform: Transform::from_xyz(event.position.x, event.position.y, -2.0),
                 ..default()
             })
-            //Create and insert an animation
-            .insert(Animation(benimator::Animation::once(
-                benimator::Animation::from_indices(0..=4, benimator::FrameRate::from_fps(16.0)),
-            )))
-            // Insert the state
-            .insert(AnimationState::default());
-    }
-}
-
-pub fn contact_animation(
-    time: Res<Time>,
-    mut query: Query<(&mut AnimationState, &mut TextureAtlasSprite, &Animation), Without<M4>>,
-) {
-    for (mut anim_state, mut texture, animation) in query.iter_mut() {
-        // Update the state
-        anim_state.update(animation, time.delta());
-
-        // Update the texture atlas
-        texture.index = anim_state.frame_index();
+            .insert(
+                SpriteAnimator::new()
+                    .with_clip("impact", 0..=4, 16.0, PlaybackMode::Once)
+                    .autoplay("impact"),
+            )
+            .insert(EffectLifecycle::UntilAnimationEnds);
     }
 }
 
@@ -68,13 +83,25 @@ pub fn fire_spawn(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut textures: ResMut<Assets<TextureAtlas>>,
-    cursor_pos: Query<&Transform, With<CursorCrosshair>>,
-    m4_pos: Query<&Transform, With<M4>>,
+    accessibility: Res<Accessibility>,
+    cursor_pos: Query<(&Transform, &Player), With<CursorCrosshair>>,
+    m4_pos: Query<(&Transform, &Player), With<M4>>,
     mut m4_event_reader: EventReader<M4AnimationEvent>,
 ) {
-    for _event in m4_event_reader.iter() {
+    for event in m4_event_reader.iter() {
         let mut rng = rand::thread_rng();
 
+        //effects come out of the gun that fired
+        let Some((cursor_pos, _)) = cursor_pos
+            .iter()
+            .find(|(_, player)| **player == event.player)
+        else {
+            continue;
+        };
+        let Some((m4_pos, _)) = m4_pos.iter().find(|(_, player)| **player == event.player) else {
+            continue;
+        };
+
         //fire effect spawner
         commands
             // Spawn a bevy sprite-sheet
@@ -88,25 +115,26 @@ pub fn fire_spawn(
                     None,
                 )),
                 transform: Transform::from_xyz(
-                    cursor_pos.single().translation.x + 150.0,
-                    cursor_pos.single().translation.y - 100.0,
+                    cursor_pos.translation.x + 150.0,
+                    cursor_pos.translation.y - 100.0,
                     -1.0,
                 ),
                 sprite: TextureAtlasSprite {
-                    color: Color::rgb(5.0, 5.0, 0.0),
+                    color: if accessibility.reduced_flash {
+                        REDUCED_FIRE_FLASH_COLOR
+                    } else {
+                        FIRE_FLASH_COLOR
+                    },
                     ..default()
                 },
                 ..default()
             })
-            //Create and insert an animation
-            .insert(Animation(benimator::Animation::once(
-                benimator::Animation::from_indices(0..=2, benimator::FrameRate::from_fps(24.0)),
-            )))
-            // Insert the state
-            .insert(BulletCase {
-                lifetime: Timer::from_seconds(0.2, TimerMode::Once),
-            })
-            .insert(AnimationState::default())
+            .insert(
+                SpriteAnimator::new()
+                    .with_clip("flash", 0..=2, 24.0, PlaybackMode::Once)
+                    .autoplay("flash"),
+            )
+            .insert(EffectLifecycle::UntilAnimationEnds)
             .insert(InGameEntity);
 
         //bullet case spawner
@@ -114,46 +142,131 @@ pub fn fire_spawn(
             .spawn(SpriteBundle {
                 texture: asset_server.load("sprites/bullet_case.png"),
                 transform: Transform::from_xyz(
-                    m4_pos.single().translation.x,
-                    m4_pos.single().translation.y + 200.0,
+                    m4_pos.translation.x,
+                    m4_pos.translation.y + 200.0,
                     -1.0,
                 ),
                 ..default()
             })
-            .insert(RigidBody::KinematicVelocityBased)
+            .insert(RigidBody::Dynamic)
+            .insert(Collider::cuboid(30.0, 12.0))
+            //casings only hit the floor, the walls and each other
+            .insert(CollisionGroups::new(CASING_GROUP, CASING_GROUP))
+            .insert(ActiveEvents::COLLISION_EVENTS)
+            .insert(GravityScale(CASING_GRAVITY_SCALE))
+            .insert(Restitution::coefficient(0.4))
             .insert(Velocity {
-                linvel: Vec2::new(rng.gen_range(4500.0..5500.0), 1000.0),
+                linvel: Vec2::new(rng.gen_range(500.0..800.0), rng.gen_range(500.0..800.0)),
                 angvel: rng.gen_range(-15.0..-5.0),
-            });
+            })
+            .insert(BulletCase {
+                lifetime: Timer::from_seconds(CASING_LIFETIME, TimerMode::Once),
+            })
+            .insert(EffectLifecycle::UntilOffScreen);
     }
 }
 
-pub fn fire_animation(
+pub fn bullet_case_fade(
+    mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(&mut AnimationState, &mut TextureAtlasSprite, &Animation), With<BulletCase>>,
+    bullet_time: Res<BulletTime>,
+    mut casings: Query<(Entity, &mut BulletCase, &mut Sprite)>,
 ) {
-    for (mut anim_state, mut texture, animation) in query.iter_mut() {
-        // Update the state
-        anim_state.update(animation, time.delta());
+    //the oldest casings make room for new ones
+    let mut by_age: Vec<_> = casings.iter_mut().collect();
+    by_age.sort_by_key(|(_, casing, _)| Reverse(casing.lifetime.elapsed()));
+    let excess = by_age.len().saturating_sub(MAX_CASINGS);
 
-        // Update the texture atlas
-        texture.index = anim_state.frame_index();
+    for (_, casing, _) in by_age.iter_mut().take(excess) {
+        let fade_start = casing.lifetime.duration().as_secs_f32() - CASING_FADE;
+        if casing.lifetime.elapsed_secs() < fade_start {
+            casing
+                .lifetime
+                .set_elapsed(Duration::from_secs_f32(fade_start));
+        }
+    }
+
+    for (entity, casing, sprite) in by_age.iter_mut() {
+        casing.lifetime.tick(bullet_time.scaled(time.delta()));
+
+        if casing.lifetime.finished() {
+            commands.entity(*entity).despawn();
+            continue;
+        }
+
+        let remaining = casing.lifetime.remaining_secs();
+        sprite.color.set_a((remaining / CASING_FADE).min(1.0));
     }
 }
 
-pub fn bullet_case_despawn(
-    mut commands: Commands,
+pub fn bullet_case_impact(
     asset_server: Res<AssetServer>,
     audio: Res<Audio>,
-    mut casing: Query<(Entity, &mut BulletCase)>,
-    time: Res<Time>,
+    casings: Query<&Velocity, With<BulletCase>>,
+    mut collision_events: EventReader<CollisionEvent>,
 ) {
-    for (casing_entity, mut casing_timer) in &mut casing {
-        casing_timer.lifetime.tick(time.delta());
+    for event in collision_events.iter() {
+        let CollisionEvent::Started(first, second, flags) = event else {
+            continue;
+        };
 
-        if casing_timer.lifetime.finished() {
-            commands.entity(casing_entity).despawn();
-            audio.play(asset_server.load("sounds/casing.ogg"));
+        //passing through the crosshair isn't an impact
+        if flags.contains(CollisionEventFlags::SENSOR) {
+            continue;
         }
+
+        //a casing landing on another one still only clinks once
+        let Some(velocity) = casings.get(*first).or(casings.get(*second)).ok() else {
+            continue;
+        };
+
+        let speed = velocity.linvel.length();
+        if speed < CASING_QUIET_SPEED {
+            continue;
+        }
+
+        let volume = ((speed - CASING_QUIET_SPEED) / (CASING_LOUD_SPEED - CASING_QUIET_SPEED))
+            .clamp(0.1, 1.0);
+        audio
+            .play(asset_server.load("sounds/casing.ogg"))
+            .with_volume(volume as f64);
+    }
+}
+
+pub fn effect_cleanup(
+    mut commands: Commands,
+    rules: Res<RunRules>,
+    effects: Query<(
+        Entity,
+        &EffectLifecycle,
+        &Transform,
+        Option<&SpriteAnimator>,
+    )>,
+) {
+    for (entity, lifecycle, transform, animation) in &effects {
+        let done = match lifecycle {
+            EffectLifecycle::UntilAnimationEnds => {
+                animation.is_none_or(|animation| animation.is_finished())
+            }
+            EffectLifecycle::UntilOffScreen => {
+                let position = transform.translation.truncate();
+                //split-screen has a playfield per player, the effect can be in either
+                !rules.players().iter().any(|&player| {
+                    let offset = (position - rules.origin(player)).abs();
+                    offset.x <= SCREEN_WIDTH / 2.0 + EFFECT_BOUNDS_MARGIN
+                        && offset.y <= SCREEN_HEIGHT / 2.0 + EFFECT_BOUNDS_MARGIN
+                })
+            }
+        };
+
+        if done {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn effect_despawner(mut commands: Commands, effects: Query<Entity, With<EffectLifecycle>>) {
+    for entity in &effects {
+        commands.entity(entity).despawn_recursive();
     }
 }
@@ -0,0 +1,423 @@
+#![allow(clippy::complexity)]
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::ingame::spawn_ball;
+use crate::ingame::Ball;
+use crate::ingame::BallBaseColor;
+use crate::ingame::BulletTime;
+use crate::ingame::DifficultyRamp;
+use crate::ingame::GameRng;
+use crate::ingame::JumpBallEvent;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::ingame::DEATH_LINE;
+
+//score needed between ball changes when rotating
+const ROTATE_EVERY: i32 = 10;
+const ERRATIC_IMPULSE: f32 = 350000.0;
+//dark enough to read on every tint and ball sprite
+const MARKER_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BallKind {
+    Normal,
+    Heavy,
+    Beach,
+    Splitting,
+    Erratic,
+}
+
+//physics and scoring rules of a ball kind, applied on top of the difficulty
+pub struct BallProfile {
+    pub gravity_multiplier: f32,
+    pub density_multiplier: f32,
+    pub impulse_multiplier: f32,
+    pub linear_damping: f32,
+    pub restitution: f32,
+    pub points: i32,
+    //none keeps the difficulty tint
+    pub tint: Option<Color>,
+    //drawn on the ball, so kinds don't rely on color alone
+    pub marker: Option<MarkerShape>,
+}
+
+#[derive(Clone, Copy)]
+pub enum MarkerShape {
+    Diamond,
+    Dot,
+    //two dots, the ball breaks in two
+    Pair,
+    Triangle,
+}
+
+#[derive(Component)]
+pub struct BallMarker;
+
+impl BallKind {
+    pub const ALL: [BallKind; 5] = [
+        BallKind::Normal,
+        BallKind::Heavy,
+        BallKind::Beach,
+        BallKind::Splitting,
+        BallKind::Erratic,
+    ];
+
+    pub fn profile(&self) -> BallProfile {
+        match self {
+            BallKind::Normal => BallProfile {
+                gravity_multiplier: 1.0,
+                density_multiplier: 1.0,
+                impulse_multiplier: 1.0,
+                linear_damping: 0.0,
+                restitution: 1.0,
+                points: 1,
+                tint: None,
+                marker: None,
+            },
+            BallKind::Heavy => BallProfile {
+                gravity_multiplier: 1.3,
+                density_multiplier: 2.0,
+                impulse_multiplier: 1.7,
+                linear_damping: 0.0,
+                restitution: 0.6,
+                points: 2,
+                tint: Some(Color::rgb(0.55, 0.55, 0.65)),
+                marker: Some(MarkerShape::Diamond),
+            },
+            BallKind::Beach => BallProfile {
+                gravity_multiplier: 0.6,
+                density_multiplier: 0.5,
+                impulse_multiplier: 0.45,
+                linear_damping: 1.5,
+                restitution: 1.0,
+                points: 1,
+                tint: Some(Color::rgb(1.8, 1.6, 0.4)),
+                marker: Some(MarkerShape::Dot),
+            },
+            BallKind::Splitting => BallProfile {
+                gravity_multiplier: 1.0,
+                density_multiplier: 1.0,
+                impulse_multiplier: 1.0,
+                linear_damping: 0.0,
+                restitution: 1.0,
+                points: 1,
+                tint: Some(Color::rgb(1.4, 0.5, 1.8)),
+                marker: Some(MarkerShape::Pair),
+            },
+            BallKind::Erratic => BallProfile {
+                gravity_multiplier: 1.0,
+                density_multiplier: 1.0,
+                impulse_multiplier: 1.0,
+                linear_damping: 0.0,
+                restitution: 1.0,
+                points: 3,
+                tint: Some(Color::rgb(1.8, 0.9, 0.2)),
+                marker: Some(MarkerShape::Triangle),
+            },
+        }
+    }
+
+    //translation key of the kind's name
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            BallKind::Normal => "ball_normal",
+            BallKind::Heavy => "ball_heavy",
+            BallKind::Beach => "ball_beach",
+            BallKind::Splitting => "ball_split",
+            BallKind::Erratic => "ball_erratic",
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> BallKind {
+        BallKind::ALL[rng.gen_range(0..BallKind::ALL.len())]
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BallSelection {
+    Fixed(BallKind),
+    Rotating,
+}
+
+impl Default for BallSelection {
+    fn default() -> Self {
+        BallSelection::Fixed(BallKind::Normal)
+    }
+}
+
+impl BallSelection {
+    pub fn starting_kind(&self, rng: &mut impl Rng) -> BallKind {
+        match self {
+            BallSelection::Fixed(kind) => *kind,
+            BallSelection::Rotating => BallKind::random(rng),
+        }
+    }
+
+    //menu order: every fixed kind, then rotating
+    pub fn next(&self) -> BallSelection {
+        match self {
+            BallSelection::Fixed(kind) => {
+                let index = BallKind::ALL.iter().position(|k| k == kind).unwrap_or(0);
+
+                match BallKind::ALL.get(index + 1) {
+                    Some(next_kind) => BallSelection::Fixed(*next_kind),
+                    None => BallSelection::Rotating,
+                }
+            }
+            BallSelection::Rotating => BallSelection::Fixed(BallKind::ALL[0]),
+        }
+    }
+
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            BallSelection::Fixed(kind) => kind.text_key(),
+            BallSelection::Rotating => "ball_random",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct BallRotation {
+    pub stage: i32,
+}
+
+pub fn ball_rotation_reset(mut rotation: ResMut<BallRotation>) {
+    rotation.stage = 0;
+}
+
+//switch every ball to a new random kind each ROTATE_EVERY points
+pub fn ball_rotation(
+    rules: Res<RunRules>,
+    scores: Res<Scores>,
+    ramp: Res<DifficultyRamp>,
+    mut rng: ResMut<GameRng>,
+    mut rotation: ResMut<BallRotation>,
+    mut balls: Query<(
+        &Ball,
+        &BallBaseColor,
+        &mut BallKind,
+        &mut GravityScale,
+        &mut ColliderMassProperties,
+        &mut Restitution,
+        &mut Damping,
+        &mut Sprite,
+    )>,
+) {
+    if rules.ball != BallSelection::Rotating {
+        return;
+    }
+
+    let stage = scores.current_score / ROTATE_EVERY;
+    if stage <= rotation.stage {
+        return;
+    }
+    rotation.stage = stage;
+
+    for (
+        ball,
+        base_color,
+        mut kind,
+        mut gravity,
+        mut mass,
+        mut restitution,
+        mut damping,
+        mut sprite,
+    ) in &mut balls
+    {
+        let mut new_kind = BallKind::random(&mut rng.0);
+        while new_kind == *kind {
+            new_kind = BallKind::random(&mut rng.0);
+        }
+
+        info!("Ball changed to {:?}", new_kind);
+        *kind = new_kind;
+
+        let profile = new_kind.profile();
+        gravity.0 = ramp.gravity() * profile.gravity_multiplier;
+        *mass = ColliderMassProperties::Density(ball.density() * profile.density_multiplier);
+        restitution.coefficient = profile.restitution;
+        damping.linear_damping = profile.linear_damping;
+        sprite.color = profile.tint.unwrap_or(base_color.0);
+    }
+}
+
+//a hit splitting ball breaks into two normal balls flying apart
+pub fn ball_split(
+    mut commands: Commands,
+    ramp: Res<DifficultyRamp>,
+    mut event_reader: EventReader<JumpBallEvent>,
+    mut balls: Query<(
+        &Ball,
+        &BallBaseColor,
+        &Handle<Image>,
+        &mut BallKind,
+        &Transform,
+        &ExternalImpulse,
+        &mut Sprite,
+        &Player,
+    )>,
+) {
+    for event in event_reader.iter() {
+        let Ok((ball, base_color, texture, mut kind, transform, impulse, mut sprite, player)) =
+            balls.get_mut(event.ball)
+        else {
+            continue;
+        };
+
+        if *kind != BallKind::Splitting {
+            continue;
+        }
+
+        *kind = BallKind::Normal;
+        sprite.color = base_color.0;
+
+        //place the new ball beside the old one so they don't start overlapped
+        let mut split_transform = *transform;
+        let direction = if impulse.impulse.x > 0.0 { -1.0 } else { 1.0 };
+        split_transform.translation.x += direction * ball.radius() * 2.0 * transform.scale.x;
+
+        let split_ball = spawn_ball(
+            &mut commands,
+            *ball,
+            BallKind::Normal,
+            &ramp,
+            split_transform,
+            texture.clone(),
+            *base_color,
+        );
+        commands
+            .entity(split_ball)
+            .insert(ExternalImpulse {
+                impulse: Vec2::new(-impulse.impulse.x, impulse.impulse.y),
+                torque_impulse: -impulse.torque_impulse,
+            })
+            .insert(*player);
+    }
+}
+
+//erratic balls get kicked sideways at random moments while airborne
+pub fn erratic_ball(
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    ramp: Res<DifficultyRamp>,
+    mut rng: ResMut<GameRng>,
+    mut timer: Local<Timer>,
+    mut balls: Query<(&BallKind, &Velocity, &mut ExternalImpulse)>,
+) {
+    timer.tick(bullet_time.scaled(time.delta()));
+
+    if !timer.finished() {
+        return;
+    }
+
+    *timer = Timer::from_seconds(rng.gen_range(0.4..1.0), TimerMode::Once);
+
+    for (kind, velocity, mut impulse) in &mut balls {
+        //don't wake up a ball that hasn't been shot yet
+        if *kind != BallKind::Erratic || velocity.linvel == Vec2::ZERO {
+            continue;
+        }
+
+        let direction = if velocity.linvel.x > 0.0 { -1.0 } else { 1.0 };
+        impulse.impulse.x += direction * ERRATIC_IMPULSE * ramp.impulse_scale();
+    }
+}
+
+//redraw the kind's marker whenever a ball gets a new kind
+pub fn ball_marker(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    balls: Query<(Entity, &Ball, &BallKind, Option<&Children>), Changed<BallKind>>,
+    markers: Query<(), With<BallMarker>>,
+) {
+    for (ball_entity, ball, kind, children) in &balls {
+        for &child in children.into_iter().flatten() {
+            if markers.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+
+        let Some(shape) = kind.profile().marker else {
+            continue;
+        };
+
+        let radius = ball.radius();
+        let parts: Vec<(Mesh, Vec2)> = match shape {
+            MarkerShape::Diamond => vec![(
+                shape::RegularPolygon::new(radius * 0.5, 4).into(),
+                Vec2::ZERO,
+            )],
+            MarkerShape::Dot => vec![(shape::Circle::new(radius * 0.35).into(), Vec2::ZERO)],
+            MarkerShape::Pair => vec![
+                (
+                    shape::Circle::new(radius * 0.22).into(),
+                    Vec2::new(-radius * 0.35, 0.0),
+                ),
+                (
+                    shape::Circle::new(radius * 0.22).into(),
+                    Vec2::new(radius * 0.35, 0.0),
+                ),
+            ],
+            MarkerShape::Triangle => vec![(
+                shape::RegularPolygon::new(radius * 0.5, 3).into(),
+                Vec2::ZERO,
+            )],
+        };
+
+        let material = materials.add(ColorMaterial::from(MARKER_COLOR));
+
+        commands.entity(ball_entity).with_children(|parent| {
+            for (mesh, offset) in parts {
+                parent
+                    .spawn(MaterialMesh2dBundle {
+                        mesh: meshes.add(mesh).into(),
+                        material: material.clone(),
+                        transform: Transform::from_xyz(offset.x, offset.y, 0.1),
+                        ..default()
+                    })
+                    .insert(BallMarker);
+            }
+        });
+    }
+}
+
+//extra balls from splits just disappear when they drop, the last one ends the game
+pub fn fallen_ball_despawner(
+    mut commands: Commands,
+    rules: Res<RunRules>,
+    balls: Query<(Entity, &Transform, &Player), With<Ball>>,
+) {
+    //respawning balls are put back in play instead
+    if rules.respawn {
+        return;
+    }
+
+    //split-screen players each keep their own last ball
+    for player in Player::ALL {
+        let mut alive = balls
+            .iter()
+            .filter(|(_, transform, owner)| {
+                **owner == player && transform.translation.y >= DEATH_LINE
+            })
+            .count();
+
+        for (ball_entity, transform, owner) in &balls {
+            if *owner != player {
+                continue;
+            }
+
+            if transform.translation.y < DEATH_LINE && alive > 0 {
+                commands.entity(ball_entity).despawn_recursive();
+            } else if transform.translation.y < DEATH_LINE {
+                //keep one fallen ball around for the game over check
+                alive += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_kira_audio::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::ingame::Ball;
+use crate::ingame::InGameEntity;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::DEATH_LINE;
+use crate::ingame::SPLIT_CAMERA_SCALE;
+use crate::localization::Localization;
+use crate::SCREEN_HEIGHT;
+use crate::SCREEN_WIDTH;
+
+const INDICATOR_COLOR: Color = Color::rgb(2.0, 1.8, 0.4);
+//arrow shrinks the further up the ball is, down to the minimum scale
+const INDICATOR_SHRINK_HEIGHT: f32 = 2000.0;
+const INDICATOR_MIN_SCALE: f32 = 0.5;
+//seconds a falling ball is from the death line when the warning starts, full danger on it
+const DANGER_SECONDS: f32 = 0.8;
+const DANGER_COLOR: Color = Color::rgba(0.9, 0.05, 0.05, 0.0);
+const DANGER_MAX_ALPHA: f32 = 0.6;
+//cue plays once the danger passes this, and again only after it calmed down
+const DANGER_CUE_THRESHOLD: f32 = 0.5;
+const DANGER_SOUND: &str = "sounds/danger.ogg";
+
+#[derive(Component)]
+pub struct OffScreenIndicator {
+    ball: Entity,
+}
+
+#[derive(Component)]
+pub struct OffScreenHeightText;
+
+//one per playfield, split-screen players only get warned about their own ball
+#[derive(Component)]
+pub struct DangerVignette(Player);
+
+//top edge of what the player's camera shows
+fn visible_top(rules: &RunRules, player: Player) -> f32 {
+    let scale = if rules.split_screen {
+        SPLIT_CAMERA_SCALE
+    } else {
+        1.0
+    };
+
+    rules.origin(player).y + SCREEN_HEIGHT / 2.0 * scale
+}
+
+pub fn offscreen_indicator_spawn(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    balls: Query<Entity, Added<Ball>>,
+) {
+    for ball in &balls {
+        commands
+            .spawn(MaterialMesh2dBundle {
+                mesh: meshes
+                    .add(shape::RegularPolygon::new(25.0, 3).into())
+                    .into(),
+                material: materials.add(ColorMaterial::from(INDICATOR_COLOR)),
+                visibility: Visibility::Hidden,
+                ..default()
+            })
+            .insert(OffScreenIndicator { ball })
+            .insert(InGameEntity)
+            .with_children(|parent| {
+                parent
+                    .spawn(Text2dBundle {
+                        text: Text::from_section(
+                            "",
+                            TextStyle {
+                                font: localization.font(&asset_server),
+                                font_size: 28.0,
+                                color: INDICATOR_COLOR,
+                            },
+                        ),
+                        transform: Transform::from_xyz(0.0, -45.0, 0.0),
+                        ..default()
+                    })
+                    .insert(OffScreenHeightText);
+            });
+    }
+}
+
+pub fn offscreen_indicator_update(
+    mut commands: Commands,
+    rules: Res<RunRules>,
+    balls: Query<(&Transform, &Player), With<Ball>>,
+    mut indicators: Query<
+        (
+            Entity,
+            &OffScreenIndicator,
+            &mut Transform,
+            &mut Visibility,
+            &Children,
+        ),
+        Without<Ball>,
+    >,
+    mut texts: Query<&mut Text, With<OffScreenHeightText>>,
+) {
+    for (entity, indicator, mut transform, mut visibility, children) in &mut indicators {
+        //the ball dropped or was despawned
+        let Ok((ball_transform, player)) = balls.get(indicator.ball) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let top = visible_top(&rules, *player);
+        let height = ball_transform.translation.y - top;
+
+        if height <= 0.0 {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        //stay inside the playfield, the walls cover its sides
+        let origin = rules.origin(*player);
+        let half_width = SCREEN_WIDTH / 2.0 - 120.0;
+        let x = ball_transform
+            .translation
+            .x
+            .clamp(origin.x - half_width, origin.x + half_width);
+
+        let scale = (1.0 - height / INDICATOR_SHRINK_HEIGHT).max(INDICATOR_MIN_SCALE);
+        transform.translation = Vec3::new(x, top - 40.0 * scale, 10.0);
+        transform.scale = Vec3::splat(scale);
+
+        for &child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.sections[0].value = format!("{:.0}", height);
+            }
+        }
+    }
+}
+
+pub fn danger_vignette_setup(mut commands: Commands, rules: Res<RunRules>) {
+    let players = rules.players();
+    let width = 100.0 / players.len() as f32;
+
+    //a red frame around each playfield, invisible until the ball falls
+    for &player in players {
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(width * player.index() as f32),
+                    width: Val::Percent(width),
+                    height: Val::Percent(100.0),
+                    border: UiRect::all(Val::Px(40.0)),
+                    ..default()
+                },
+                border_color: BorderColor(DANGER_COLOR),
+                ..default()
+            })
+            .insert(DangerVignette(player))
+            .insert(InGameEntity);
+    }
+}
+
+pub fn danger_warning(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    time: Res<Time>,
+    rules: Res<RunRules>,
+    balls: Query<(&Transform, &Velocity, &Player), With<Ball>>,
+    mut vignettes: Query<(&mut BorderColor, &DangerVignette)>,
+    mut warned: Local<[bool; 2]>,
+) {
+    //fallen balls come back in practice, nothing to warn about
+    if rules.respawn {
+        return;
+    }
+
+    //the falling ball closest to dropping decides, fast balls warn from higher up
+    let mut danger = [0.0_f32; 2];
+    for (transform, velocity, player) in &balls {
+        let height = transform.translation.y - DEATH_LINE;
+
+        //balls already past the line are lost anyway
+        if velocity.linvel.y >= 0.0 || height < 0.0 {
+            continue;
+        }
+
+        let seconds_left = height / -velocity.linvel.y;
+        let ball_danger = (1.0 - seconds_left / DANGER_SECONDS).clamp(0.0, 1.0);
+        danger[player.index()] = danger[player.index()].max(ball_danger);
+    }
+
+    for (mut border_color, vignette) in &mut vignettes {
+        let index = vignette.0.index();
+
+        if danger[index] >= DANGER_CUE_THRESHOLD && !warned[index] {
+            audio.play(asset_server.load(DANGER_SOUND));
+            warned[index] = true;
+        } else if danger[index] == 0.0 {
+            warned[index] = false;
+        }
+
+        //pulse faster the closer the ball gets
+        let pulse = 0.75 + 0.25 * (time.elapsed_seconds() * (6.0 + 10.0 * danger[index])).sin();
+
+        border_color
+            .0
+            .set_a(danger[index] * pulse * DANGER_MAX_ALPHA);
+    }
+}
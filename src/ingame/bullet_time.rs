@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::ingame::RunRules;
+use crate::ingame::ShotFiredEvent;
+
+//how much the game slows down while bullet time is active
+pub const BULLET_TIME_SCALE: f32 = 0.3;
+//meter gained per hit, multiplied by the current hit streak
+const METER_PER_HIT: f32 = 0.05;
+const MAX_STREAK_MULTIPLIER: u32 = 5;
+//meter needed before bullet time can be activated
+const METER_ACTIVATION_MIN: f32 = 0.25;
+//meter drained per real second while active
+const METER_DRAIN_PER_SECOND: f32 = 0.3;
+const BULLET_TIME_KEY: KeyCode = KeyCode::Space;
+
+#[derive(Resource, Default)]
+pub struct BulletTime {
+    pub meter: f32,
+    pub streak: u32,
+    pub active: bool,
+}
+
+impl BulletTime {
+    pub fn time_scale(&self) -> f32 {
+        if self.active {
+            BULLET_TIME_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    //scale a frame delta so gameplay timers and animations slow down with the physics
+    pub fn scaled(&self, delta: Duration) -> Duration {
+        delta.mul_f32(self.time_scale())
+    }
+}
+
+pub fn bullet_time_reset(mut bullet_time: ResMut<BulletTime>) {
+    *bullet_time = BulletTime::default();
+}
+
+pub fn bullet_time_meter(
+    mut bullet_time: ResMut<BulletTime>,
+    mut shot_event_reader: EventReader<ShotFiredEvent>,
+) {
+    for shot in shot_event_reader.iter() {
+        if shot.hit {
+            bullet_time.streak += 1;
+
+            //meter doesn't fill while it is being drained
+            if !bullet_time.active {
+                let multiplier = bullet_time.streak.min(MAX_STREAK_MULTIPLIER) as f32;
+                bullet_time.meter = (bullet_time.meter + METER_PER_HIT * multiplier).min(1.0);
+            }
+        } else {
+            bullet_time.streak = 0;
+        }
+    }
+}
+
+pub fn bullet_time_controller(
+    input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    audio: Res<Audio>,
+    rules: Res<RunRules>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    let was_active = bullet_time.active;
+
+    if input.just_pressed(BULLET_TIME_KEY)
+        && rules.bullet_time
+        && !bullet_time.active
+        && bullet_time.meter >= METER_ACTIVATION_MIN
+    {
+        info!("Bullet time activated");
+        bullet_time.active = true;
+    }
+
+    //meter drains in real time, so slow motion lasts the same for the player
+    if bullet_time.active {
+        bullet_time.meter -= METER_DRAIN_PER_SECOND * time.delta_seconds();
+
+        if bullet_time.meter <= 0.0 {
+            bullet_time.meter = 0.0;
+            bullet_time.active = false;
+        }
+    }
+
+    if bullet_time.active != was_active {
+        apply_time_scale(bullet_time.time_scale(), &audio, &mut rapier_config);
+    }
+}
+
+//restore normal speed when leaving the game in the middle of bullet time
+pub fn bullet_time_restore(
+    audio: Res<Audio>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    bullet_time.active = false;
+    apply_time_scale(1.0, &audio, &mut rapier_config);
+}
+
+fn apply_time_scale(scale: f32, audio: &Audio, rapier_config: &mut RapierConfiguration) {
+    if let TimestepMode::Variable { time_scale, .. } = &mut rapier_config.timestep_mode {
+        *time_scale = scale;
+    }
+
+    //new sounds on the channel pick up its playback rate as well
+    audio.set_playback_rate(scale as f64);
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::ingame::BulletTime;
+use crate::ingame::MainCamera;
+use crate::ingame::RunRules;
+use crate::ingame::ShotFiredEvent;
+use crate::save::SaveData;
+
+//trauma added per shot, shake grows with trauma squared
+const SHOT_TRAUMA: f32 = 0.15;
+const HIT_TRAUMA: f32 = 0.35;
+const TRAUMA_DECAY_PER_SECOND: f32 = 1.5;
+const MAX_SHAKE_OFFSET: f32 = 18.0;
+const MAX_SHAKE_ANGLE: f32 = 0.02;
+//physics freezes this long on every hit, in real seconds
+const HIT_STOP_SECONDS: f32 = 0.05;
+//every this many hits in a row punches the camera in
+const ZOOM_PUNCH_STREAK: u32 = 10;
+const ZOOM_PUNCH_SCALE: f32 = 0.08;
+const ZOOM_PUNCH_DECAY_PER_SECOND: f32 = 2.5;
+
+#[derive(Resource, Default)]
+pub struct CameraEffects {
+    pub trauma: f32,
+    pub hit_stop: f32,
+    pub zoom_punch: f32,
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+}
+
+pub fn camera_effects_reset(
+    mut effects: ResMut<CameraEffects>,
+    rules: Res<RunRules>,
+    mut main_camera: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    *effects = CameraEffects::default();
+
+    //split-screen parks the main camera elsewhere and restores it itself
+    if rules.split_screen {
+        return;
+    }
+
+    for (mut transform, mut projection) in &mut main_camera {
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        transform.rotation = Quat::IDENTITY;
+        projection.scale = 1.0;
+    }
+}
+
+pub fn camera_effects_trigger(
+    rules: Res<RunRules>,
+    bullet_time: Res<BulletTime>,
+    mut effects: ResMut<CameraEffects>,
+    mut shot_event_reader: EventReader<ShotFiredEvent>,
+) {
+    for shot in shot_event_reader.iter() {
+        //one player's hits shouldn't shake or freeze the other's half
+        if rules.split_screen {
+            continue;
+        }
+
+        if shot.hit {
+            effects.add_trauma(HIT_TRAUMA);
+            effects.hit_stop = HIT_STOP_SECONDS;
+
+            if bullet_time.streak > 0 && bullet_time.streak.is_multiple_of(ZOOM_PUNCH_STREAK) {
+                effects.zoom_punch = 1.0;
+            }
+        } else {
+            effects.add_trauma(SHOT_TRAUMA);
+        }
+    }
+}
+
+pub fn camera_shake(
+    time: Res<Time>,
+    save_data: Res<SaveData>,
+    rules: Res<RunRules>,
+    mut effects: ResMut<CameraEffects>,
+    mut main_camera: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if rules.split_screen {
+        return;
+    }
+
+    //effects wind down in real time so bullet time doesn't drag them out
+    let delta = time.delta_seconds();
+    effects.trauma = (effects.trauma - TRAUMA_DECAY_PER_SECOND * delta).max(0.0);
+    effects.zoom_punch = (effects.zoom_punch - ZOOM_PUNCH_DECAY_PER_SECOND * delta).max(0.0);
+
+    //reduced motion keeps the camera still
+    let (shake, zoom) = if save_data.reduce_motion {
+        (0.0, 0.0)
+    } else {
+        (effects.trauma * effects.trauma, effects.zoom_punch)
+    };
+
+    let mut rng = rand::thread_rng();
+
+    for (mut transform, mut projection) in &mut main_camera {
+        transform.translation.x = MAX_SHAKE_OFFSET * shake * rng.gen_range(-1.0..1.0);
+        transform.translation.y = MAX_SHAKE_OFFSET * shake * rng.gen_range(-1.0..1.0);
+        transform.rotation =
+            Quat::from_rotation_z(MAX_SHAKE_ANGLE * shake * rng.gen_range(-1.0..1.0));
+        projection.scale = 1.0 - ZOOM_PUNCH_SCALE * zoom;
+    }
+}
+
+pub fn hit_stop(
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    mut effects: ResMut<CameraEffects>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut stopped: Local<bool>,
+) {
+    let TimestepMode::Variable { time_scale, .. } = &mut rapier_config.timestep_mode else {
+        return;
+    };
+
+    if effects.hit_stop > 0.0 {
+        effects.hit_stop -= time.delta_seconds();
+        *time_scale = 0.0;
+        *stopped = true;
+    } else if *stopped {
+        //hand the physics back at whatever speed bullet time wants
+        *time_scale = bullet_time.time_scale();
+        *stopped = false;
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use chrono::{Datelike, Local, NaiveDate};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::ingame::ball_types::{BallKind, BallSelection};
+use crate::ingame::GameMode;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::save::{DailyResult, SaveData};
+use crate::GameDifficultyState;
+
+//same ruleset for everyone playing on the same local date
+#[derive(Resource)]
+pub struct DailyChallenge {
+    pub date: String,
+    pub seed: u64,
+    pub difficulty: GameDifficultyState,
+    pub ball: BallSelection,
+    pub gravity_multiplier: f32,
+    pub bullet_time: bool,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self::for_date(Local::now().date_naive())
+    }
+
+    fn for_date(date: NaiveDate) -> Self {
+        let date_number =
+            date.year() as u64 * 10000 + date.month() as u64 * 100 + date.day() as u64;
+
+        Self::from_seed(date.format("%Y-%m-%d").to_string(), splitmix64(date_number))
+    }
+
+    fn from_seed(date: String, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let difficulty = match rng.gen_range(0..3) {
+            0 => GameDifficultyState::Easy,
+            1 => GameDifficultyState::Medium,
+            _ => GameDifficultyState::Hard,
+        };
+        let ball = if rng.gen_bool(0.25) {
+            BallSelection::Rotating
+        } else {
+            BallSelection::Fixed(BallKind::random(&mut rng))
+        };
+        //gravity in 5% steps between 85% and 120%
+        let gravity_multiplier = 0.85 + rng.gen_range(0..=7) as f32 * 0.05;
+        let bullet_time = rng.gen_bool(0.7);
+
+        Self {
+            date,
+            seed,
+            difficulty,
+            ball,
+            gravity_multiplier,
+            bullet_time,
+        }
+    }
+}
+
+impl Default for DailyChallenge {
+    fn default() -> Self {
+        Self::today()
+    }
+}
+
+//spread neighbouring dates into unrelated seeds
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+//the date might have changed while sitting in the menus
+pub fn daily_refresh(mut daily: ResMut<DailyChallenge>) {
+    let today = DailyChallenge::today();
+
+    if today.date != daily.date {
+        *daily = today;
+    }
+}
+
+pub fn daily_result_recorder(
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    daily: Res<DailyChallenge>,
+    mut scores: ResMut<Scores>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if *mode != GameMode::Daily {
+        return;
+    }
+
+    if rules.scored {
+        save_data.daily_results.push(DailyResult {
+            date: daily.date.clone(),
+            score: scores.current_score,
+            difficulty: daily.difficulty,
+            ball: daily.ball,
+            assisted: rules.assisted(),
+        });
+        save_data.store();
+    }
+
+    //game over screen shows the counted attempt of the day
+    scores.high_score = save_data
+        .daily_result(&daily.date)
+        .map_or(scores.current_score, |result| result.score);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn same_date_gives_the_same_challenge() {
+        let first = DailyChallenge::for_date(date(2024, 3, 9));
+        let second = DailyChallenge::for_date(date(2024, 3, 9));
+
+        assert_eq!(first.date, "2024-03-09");
+        assert_eq!(first.seed, second.seed);
+        assert_eq!(first.difficulty, second.difficulty);
+        assert_eq!(first.ball, second.ball);
+        assert_eq!(first.gravity_multiplier, second.gravity_multiplier);
+        assert_eq!(first.bullet_time, second.bullet_time);
+    }
+
+    #[test]
+    fn neighbouring_dates_get_unrelated_seeds() {
+        let days: Vec<u64> = (1..=28)
+            .map(|day| DailyChallenge::for_date(date(2024, 2, day)).seed)
+            .collect();
+
+        for (index, seed) in days.iter().enumerate() {
+            assert!(!days[index + 1..].contains(seed), "seed repeats: {}", seed);
+        }
+
+        //more than the last digit of the date changes
+        assert!((days[0] ^ days[1]).count_ones() > 8);
+    }
+
+    #[test]
+    fn gravity_stays_in_its_steps() {
+        for day in 1..=31 {
+            let daily = DailyChallenge::for_date(date(2024, 1, day));
+            let steps = (daily.gravity_multiplier - 0.85) / 0.05;
+
+            assert!((0.85..=1.2001).contains(&daily.gravity_multiplier));
+            assert!((steps - steps.round()).abs() < 0.001);
+        }
+    }
+}
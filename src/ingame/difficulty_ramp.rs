@@ -0,0 +1,218 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::ingame::Ball;
+use crate::ingame::BallKind;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+
+#[derive(Event)]
+pub struct LevelUpEvent {
+    pub level: u32,
+}
+
+//how a run gets harder as the score grows, one curve per difficulty
+#[derive(Clone)]
+pub struct RampCurve {
+    pub points_per_level: i32,
+    pub max_level: u32,
+    pub base_gravity: f32,
+    pub gravity_per_level: f32,
+    //ball scale lost per level, clamped by min_scale
+    pub shrink_per_level: f32,
+    pub min_scale: f32,
+    pub base_impulse_y: Range<f32>,
+    pub base_impulse_x: f32,
+    //widens the sideways range both ways per level, the upward one only up
+    pub impulse_spread_per_level: f32,
+}
+
+impl RampCurve {
+    pub const EASY: RampCurve = RampCurve {
+        points_per_level: 15,
+        max_level: 5,
+        base_gravity: 17.0,
+        gravity_per_level: 1.5,
+        shrink_per_level: 0.05,
+        min_scale: 0.75,
+        base_impulse_y: 500000.0..900000.0,
+        base_impulse_x: 500000.0,
+        impulse_spread_per_level: 40000.0,
+    };
+
+    pub const MEDIUM: RampCurve = RampCurve {
+        points_per_level: 10,
+        max_level: 8,
+        base_gravity: 30.0,
+        gravity_per_level: 2.0,
+        shrink_per_level: 0.05,
+        min_scale: 0.6,
+        base_impulse_y: 500000.0..900000.0,
+        base_impulse_x: 500000.0,
+        impulse_spread_per_level: 60000.0,
+    };
+
+    pub const HARD: RampCurve = RampCurve {
+        points_per_level: 8,
+        max_level: 10,
+        base_gravity: 24.0,
+        gravity_per_level: 2.5,
+        shrink_per_level: 0.04,
+        min_scale: 0.6,
+        base_impulse_y: 500000.0..900000.0,
+        base_impulse_x: 500000.0,
+        impulse_spread_per_level: 80000.0,
+    };
+}
+
+#[derive(Resource)]
+pub struct DifficultyRamp {
+    pub level: u32,
+    pub curve: RampCurve,
+    //run modifier on top of the curve, e.g. from the daily challenge
+    pub gravity_multiplier: f32,
+}
+
+impl DifficultyRamp {
+    pub fn new(curve: RampCurve) -> Self {
+        Self {
+            level: 0,
+            curve,
+            gravity_multiplier: 1.0,
+        }
+    }
+
+    pub fn gravity(&self) -> f32 {
+        (self.curve.base_gravity + self.curve.gravity_per_level * self.level as f32)
+            * self.gravity_multiplier
+    }
+
+    pub fn ball_scale(&self) -> f32 {
+        (1.0 - self.curve.shrink_per_level * self.level as f32).max(self.curve.min_scale)
+    }
+
+    //a smaller ball is lighter, so impulses shrink with its area to keep jumps comparable
+    pub fn impulse_scale(&self) -> f32 {
+        self.ball_scale() * self.ball_scale()
+    }
+
+    pub fn impulse_y_range(&self) -> Range<f32> {
+        let spread = self.curve.impulse_spread_per_level * self.level as f32;
+
+        //a weaker lower bound would let a clean hit push the ball down
+        self.curve.base_impulse_y.start * self.impulse_scale()
+            ..(self.curve.base_impulse_y.end + spread) * self.impulse_scale()
+    }
+
+    pub fn impulse_x_range(&self) -> Range<f32> {
+        let spread = self.curve.impulse_spread_per_level * self.level as f32;
+        let limit = (self.curve.base_impulse_x + spread) * self.impulse_scale();
+
+        -limit..limit
+    }
+}
+
+impl Default for DifficultyRamp {
+    fn default() -> Self {
+        Self::new(RampCurve::MEDIUM)
+    }
+}
+
+pub fn difficulty_ramp(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    scores: Res<Scores>,
+    rules: Res<RunRules>,
+    mut ramp: ResMut<DifficultyRamp>,
+    mut ball: Query<(&mut GravityScale, &mut Transform, &BallKind), With<Ball>>,
+    mut level_up_event: EventWriter<LevelUpEvent>,
+) {
+    if !rules.ramp {
+        return;
+    }
+
+    let level = ((scores.current_score / ramp.curve.points_per_level).max(0) as u32)
+        .min(ramp.curve.max_level);
+
+    if level <= ramp.level {
+        return;
+    }
+
+    ramp.level = level;
+    info!("Level up: {}", level);
+
+    for (mut gravity, mut transform, kind) in &mut ball {
+        gravity.0 = ramp.gravity() * kind.profile().gravity_multiplier;
+        //collider is scaled together with the sprite
+        transform.scale = Vec3::splat(ramp.ball_scale());
+    }
+
+    audio
+        .play(asset_server.load("sounds/start.ogg"))
+        .with_playback_rate(1.5);
+    level_up_event.send(LevelUpEvent { level });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [RampCurve; 3] = [RampCurve::EASY, RampCurve::MEDIUM, RampCurve::HARD];
+
+    fn levels(curve: &RampCurve) -> impl Iterator<Item = DifficultyRamp> + '_ {
+        (0..=curve.max_level).map(|level| DifficultyRamp {
+            level,
+            ..DifficultyRamp::new(curve.clone())
+        })
+    }
+
+    #[test]
+    fn upward_impulse_stays_positive() {
+        for curve in &CURVES {
+            for ramp in levels(curve) {
+                let range = ramp.impulse_y_range();
+
+                assert!(range.start > 0.0, "level {}: {:?}", ramp.level, range);
+                assert!(range.start < range.end, "level {}: {:?}", ramp.level, range);
+            }
+        }
+    }
+
+    #[test]
+    fn sideways_impulse_is_symmetric_and_non_empty() {
+        for curve in &CURVES {
+            for ramp in levels(curve) {
+                let range = ramp.impulse_x_range();
+
+                assert!(range.end > 0.0, "level {}: {:?}", ramp.level, range);
+                assert_eq!(range.start, -range.end);
+            }
+        }
+    }
+
+    #[test]
+    fn ball_shrinks_down_to_the_minimum_scale() {
+        for curve in &CURVES {
+            let scales: Vec<f32> = levels(curve).map(|ramp| ramp.ball_scale()).collect();
+
+            assert_eq!(scales[0], 1.0);
+            assert!(scales.windows(2).all(|pair| pair[1] <= pair[0]));
+            assert!(scales.iter().all(|scale| *scale >= curve.min_scale));
+        }
+    }
+
+    #[test]
+    fn gravity_grows_per_level_and_follows_the_multiplier() {
+        let mut ramp = DifficultyRamp::new(RampCurve::HARD);
+        assert_eq!(ramp.gravity(), 24.0);
+
+        ramp.level = 2;
+        assert_eq!(ramp.gravity(), 29.0);
+
+        ramp.gravity_multiplier = 2.0;
+        assert_eq!(ramp.gravity(), 58.0);
+    }
+}
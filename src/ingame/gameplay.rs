@@ -1,145 +1,238 @@
-#![allow(clippy::too_many_arguments)]
+#![allow(clippy::complexity, clippy::too_many_arguments)]
 
-use bevy::{prelude::*, window::CursorGrabMode};
+use bevy::{prelude::*, utils::HashSet, window::CursorGrabMode};
 use bevy_kira_audio::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
 
-use crate::ingame::Animation;
-use crate::ingame::AnimationState;
+use crate::ingame::gamepad_fire_pressed;
 use crate::ingame::Ball;
+use crate::ingame::BallKind;
+use crate::ingame::BulletTime;
 use crate::ingame::CursorCrosshair;
+use crate::ingame::DifficultyRamp;
 use crate::ingame::EndGameTimer;
+use crate::ingame::GameMode;
+use crate::ingame::GameRng;
 use crate::ingame::InGameEntity;
+use crate::ingame::MainCamera;
+use crate::ingame::Player;
+use crate::ingame::PlayerCamera;
+use crate::ingame::RunRules;
 use crate::ingame::Scores;
+use crate::ingame::SpriteAnimator;
+use crate::ingame::Versus;
+use crate::ingame::Wall;
 use crate::ingame::M4;
 use crate::AppState;
+use crate::GameDifficultyState;
 
-#[derive(Resource)]
-pub struct PlayAnimation(pub bool);
+//balls below this height are lost
+pub const DEATH_LINE: f32 = -420.0;
 
 #[derive(Event)]
-pub struct JumpBallEvent;
+pub struct JumpBallEvent {
+    pub ball: Entity,
+}
 
 #[derive(Event)]
-pub struct ContactAnimationEvent;
+pub struct ContactAnimationEvent {
+    pub position: Vec2,
+}
 
 #[derive(Event)]
-pub struct M4AnimationEvent;
+pub struct M4AnimationEvent {
+    pub player: Player,
+}
+
+#[derive(Event)]
+pub struct WallHitEvent {
+    pub ball: Entity,
+    pub wall: Entity,
+    pub position: Vec2,
+    pub speed: f32,
+    //points away from the wall, into the playfield
+    pub normal: Vec2,
+}
+
+#[derive(Event)]
+pub struct ShotFiredEvent {
+    pub hit: bool,
+    pub player: Player,
+}
 
 pub fn cursor_position(
-    mut crosshair: Query<&mut Transform, With<CursorCrosshair>>,
-    mut m4: Query<&mut Transform, (With<M4>, Without<CursorCrosshair>)>,
-    camera_q: Query<(&Camera, &GlobalTransform)>,
+    mut crosshair: Query<(&mut Transform, &Player), With<CursorCrosshair>>,
+    mut m4: Query<(&mut Transform, &Player), (With<M4>, Without<CursorCrosshair>)>,
+    main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    player_cameras: Query<(&Camera, &GlobalTransform, &PlayerCamera)>,
     windows: Query<&Window>,
 ) {
-    // alternative mouse position finder
-    let (camera, camera_transform) = camera_q.single();
+    //in split-screen the mouse aims in player one's half
+    let (camera, camera_transform) = player_cameras
+        .iter()
+        .find(|(_, _, PlayerCamera(player))| *player == Player::One)
+        .map(|(camera, camera_transform, _)| (camera, camera_transform))
+        .unwrap_or_else(|| main_camera.single());
 
-    let Some(mouse_position) = windows
+    // alternative mouse position finder
+    let mouse_position = windows
         .single()
         .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
-    else {
-        return;
-    };
+        .map(|cursor| {
+            camera
+                .logical_viewport_size()
+                .map_or(cursor, |size| cursor.min(size))
+        })
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
 
-    let mut m4_position = m4.single_mut();
+    for (mut crosshair_pos, player) in &mut crosshair {
+        //crosshair position, player two aims with the gamepad
+        if let (Player::One, Some(mouse_position)) = (player, mouse_position) {
+            crosshair_pos.translation.x = mouse_position.x;
+            crosshair_pos.translation.y = mouse_position.y;
+        }
 
-    for mut crosshair_pos in &mut crosshair {
-        //crosshair position
-        crosshair_pos.translation.x = mouse_position.x;
-        crosshair_pos.translation.y = mouse_position.y;
         //m4 position relative to cursor position
-        m4_position.translation.x = mouse_position.x + 350.0;
-        m4_position.translation.y = mouse_position.y - 400.0;
+        for (mut m4_position, m4_player) in &mut m4 {
+            if m4_player == player {
+                m4_position.translation.x = crosshair_pos.translation.x + 350.0;
+                m4_position.translation.y = crosshair_pos.translation.y - 400.0;
+            }
+        }
     }
 }
 
 pub fn ball_movement(
     mut scores: ResMut<Scores>,
-    mut ball: Query<(&mut ExternalImpulse, &mut Velocity), With<Ball>>,
+    ramp: Res<DifficultyRamp>,
+    mut rng: ResMut<GameRng>,
+    mut ball: Query<(&mut ExternalImpulse, &mut Velocity, &BallKind, &Player), With<Ball>>,
     mut event_reader: EventReader<JumpBallEvent>,
 ) {
     //jump ball if collide eachother
-    for _event in event_reader.iter() {
-        for (mut ball_impulse, mut ball_velocity) in &mut ball {
-            let mut rng = rand::thread_rng();
+    for event in event_reader.iter() {
+        if let Ok((mut ball_impulse, mut ball_velocity, kind, player)) = ball.get_mut(event.ball) {
+            let profile = kind.profile();
+            let impulse_scale = ramp.impulse_scale() * profile.impulse_multiplier;
 
-            scores.current_score += 1;
-            info!("{}", scores.current_score);
+            *scores.player_score_mut(*player) += profile.points;
+            info!("{}", scores.player_score(*player));
             ball_velocity.linvel.y = 0.0;
             ball_velocity.linvel.x = 0.0;
             ball_velocity.angvel = 0.0;
-            ball_impulse.impulse.y = rng.gen_range(500000.0..900000.0);
-            ball_impulse.impulse.x = rng.gen_range(-500000.0..500000.0);
-            ball_impulse.torque_impulse = rng.gen_range(-10000000.0..10000000.0);
+            ball_impulse.impulse.y =
+                rng.gen_range(ramp.impulse_y_range()) * profile.impulse_multiplier;
+            ball_impulse.impulse.x =
+                rng.gen_range(ramp.impulse_x_range()) * profile.impulse_multiplier;
+            ball_impulse.torque_impulse = rng.gen_range(-10000000.0..10000000.0) * impulse_scale;
+        }
+    }
+}
+
+//only the first frame of a contact counts as a hit
+pub fn wall_contact_checker(
+    rapier_context: Res<RapierContext>,
+    balls: Query<(Entity, &Transform, &Velocity, &Ball)>,
+    walls: Query<(Entity, &Transform), With<Wall>>,
+    mut touching: Local<HashSet<(Entity, Entity)>>,
+    mut wall_hit_writer: EventWriter<WallHitEvent>,
+) {
+    let mut still_touching = HashSet::new();
+
+    for (ball_entity, ball_transform, ball_velocity, ball) in &balls {
+        for (wall_entity, wall_transform) in &walls {
+            let in_contact = rapier_context
+                .contact_pair(ball_entity, wall_entity)
+                .is_some_and(|contact| contact.has_any_active_contacts());
+
+            if !in_contact {
+                continue;
+            }
+
+            still_touching.insert((ball_entity, wall_entity));
+            if touching.contains(&(ball_entity, wall_entity)) {
+                continue;
+            }
+
+            let side = (ball_transform.translation.x - wall_transform.translation.x).signum();
+            let normal = Vec2::new(side, 0.0);
+            wall_hit_writer.send(WallHitEvent {
+                ball: ball_entity,
+                wall: wall_entity,
+                position: ball_transform.translation.truncate() - normal * ball.radius(),
+                speed: ball_velocity.linvel.length(),
+                normal,
+            });
         }
     }
+
+    *touching = still_touching;
 }
 
 pub fn ball_contact_checker(
     asset_server: Res<AssetServer>,
     audio: Res<Audio>,
     input: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
     rapier_context: Res<RapierContext>,
     ball: Query<Entity, With<Ball>>,
-    crosshair: Query<Entity, With<CursorCrosshair>>,
-    mut m4: Query<&mut M4>,
-    mut play_animation: ResMut<PlayAnimation>,
+    crosshair: Query<(Entity, &Transform, &Player), With<CursorCrosshair>>,
+    mut m4: Query<(&mut M4, &mut SpriteAnimator, &Player)>,
     mut event_writer: EventWriter<JumpBallEvent>,
     mut contact_event_writer: EventWriter<ContactAnimationEvent>,
     mut m4_animation_event: EventWriter<M4AnimationEvent>,
+    mut shot_event_writer: EventWriter<ShotFiredEvent>,
 ) {
-    let ball_entity = ball.single();
-    let cross_entity = crosshair.single();
-    let mut m4_props = m4.single_mut();
-
-    if input.just_pressed(MouseButton::Left) && m4_props.okay_to_shoot {
-        //m4 sound play and 0 rate of fire
-        m4_props.okay_to_shoot = false;
-        play_animation.0 = true;
-        m4_animation_event.send(M4AnimationEvent);
-        info!("{:?}", play_animation.0);
-        audio.play(asset_server.load("sounds/M4.ogg"));
-
-        //check jump ball collide
-        if rapier_context.intersection_pair(ball_entity, cross_entity) == Some(true) {
-            event_writer.send(JumpBallEvent);
-            contact_event_writer.send(ContactAnimationEvent);
-        }
-    }
-}
+    for (cross_entity, cross_transform, player) in &crosshair {
+        let trigger_pressed = match player {
+            Player::One => input.just_pressed(MouseButton::Left),
+            Player::Two => gamepad_fire_pressed(&gamepads, &gamepad_input),
+        };
+
+        let Some((mut m4_props, mut m4_animation, _)) =
+            m4.iter_mut().find(|(_, _, m4_player)| *m4_player == player)
+        else {
+            continue;
+        };
 
-pub fn m4_firerate_timer(mut m4: Query<&mut M4>, time: Res<Time>) {
-    let mut m4_timer = m4.single_mut();
+        if trigger_pressed && m4_props.okay_to_shoot {
+            //m4 sound play and 0 rate of fire
+            m4_props.okay_to_shoot = false;
+            m4_animation.play("fire");
+            m4_animation_event.send(M4AnimationEvent { player: *player });
+            audio.play(asset_server.load("sounds/M4.ogg"));
 
-    if !m4_timer.okay_to_shoot {
-        m4_timer.lifetime.tick(time.delta());
+            //check jump ball collide, one bullet only jumps one ball
+            let hit_ball = ball.iter().find(|&ball_entity| {
+                rapier_context.intersection_pair(ball_entity, cross_entity) == Some(true)
+            });
+            let hit = hit_ball.is_some();
 
-        if m4_timer.lifetime.finished() {
-            m4_timer.okay_to_shoot = true;
-            m4_timer.lifetime = Timer::from_seconds(0.2, TimerMode::Once);
+            if let Some(ball_entity) = hit_ball {
+                event_writer.send(JumpBallEvent { ball: ball_entity });
+                contact_event_writer.send(ContactAnimationEvent {
+                    position: cross_transform.translation.truncate(),
+                });
+            }
+
+            shot_event_writer.send(ShotFiredEvent {
+                hit,
+                player: *player,
+            });
         }
     }
 }
 
-pub fn m4_animation(
-    time: Res<Time>,
-    mut query: Query<(&mut AnimationState, &mut TextureAtlasSprite, &Animation), With<M4>>,
-    mut play_animation: ResMut<PlayAnimation>,
-) {
-    if play_animation.0 {
-        for (mut anim_state, mut texture, animation) in query.iter_mut() {
-            // Update the state
-            anim_state.update(animation, time.delta());
-
-            // Update the texture atlas
-            texture.index = anim_state.frame_index();
+pub fn m4_firerate_timer(mut m4: Query<&mut M4>, time: Res<Time>, bullet_time: Res<BulletTime>) {
+    for mut m4_timer in &mut m4 {
+        if !m4_timer.okay_to_shoot {
+            m4_timer.lifetime.tick(bullet_time.scaled(time.delta()));
 
-            if anim_state.frame_index() == 4 {
-                play_animation.0 = false;
-                anim_state.reset();
+            if m4_timer.lifetime.finished() {
+                m4_timer.okay_to_shoot = true;
+                m4_timer.lifetime = Timer::from_seconds(0.2, TimerMode::Once);
             }
         }
     }
@@ -150,12 +243,30 @@ pub fn gameover_controller(
     mut timer: Query<&mut EndGameTimer>,
     ball: Query<&Transform, With<Ball>>,
     time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    rules: Res<RunRules>,
+    versus: Res<Versus>,
     mut windows: Query<&mut Window>,
 ) {
-    if ball.single().translation.y < -420.0 {
+    if rules.respawn {
+        return;
+    }
+
+    //split balls only end the game once every one of them dropped,
+    //split-screen ends as soon as either player drops
+    let run_over = if rules.split_screen {
+        versus.dropped.is_some()
+    } else {
+        ball.iter()
+            .all(|transform| transform.translation.y < DEATH_LINE)
+    };
+
+    if run_over {
         let mut end_game_timer = timer.single_mut();
 
-        end_game_timer.lifetime.tick(time.delta());
+        end_game_timer
+            .lifetime
+            .tick(bullet_time.scaled(time.delta()));
 
         if end_game_timer.lifetime.finished() {
             //enable cursor
@@ -171,41 +282,23 @@ pub fn gameover_controller(
 
 pub fn entity_despawner(
     mut commands: Commands,
-    ball: Query<&Ball>,
+    difficulty: Res<State<GameDifficultyState>>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
     mut entities: Query<Entity, With<InGameEntity>>,
     mut scores: ResMut<Scores>,
 ) {
-    let ball_diff = ball.single();
+    //only standard runs compete for the difficulty high scores
+    if *mode == GameMode::Standard && rules.scored {
+        let current_score = scores.current_score;
+        let high_score = scores.high_score_mut(*difficulty.get(), rules.assisted());
 
-    match ball_diff {
-        Ball::Easy => {
-            scores.high_score = scores.easy_hscore;
-
-            if scores.current_score > scores.easy_hscore {
-                scores.easy_hscore = scores.current_score;
-                scores.high_score = scores.current_score
-            }
-        }
-        Ball::Medium => {
-            scores.high_score = scores.medium_hscore;
-
-            if scores.current_score > scores.medium_hscore {
-                scores.medium_hscore = scores.current_score;
-                scores.high_score = scores.current_score
-            }
-        }
-        Ball::Hard => {
-            scores.high_score = scores.hard_hscore;
-
-            if scores.current_score > scores.hard_hscore {
-                scores.hard_hscore = scores.current_score;
-                scores.high_score = scores.current_score
-            }
-        }
+        *high_score = (*high_score).max(current_score);
+        scores.high_score = *high_score;
     }
 
-    //despawn everyting in InGame
+    //despawn everyting in InGame, untagged children like the crosshair outline included
     for entities_despawner in &mut entities {
-        commands.entity(entities_despawner).despawn();
+        commands.entity(entities_despawner).despawn_recursive();
     }
 }
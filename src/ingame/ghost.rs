@@ -0,0 +1,297 @@
+#![allow(clippy::complexity, clippy::too_many_arguments)]
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ingame::Ball;
+use crate::ingame::CursorCrosshair;
+use crate::ingame::GameMode;
+use crate::ingame::InGameEntity;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::save::SaveData;
+use crate::GameDifficultyState;
+
+//positions recorded per second, playback interpolates between them
+const GHOST_SAMPLE_RATE: f32 = 30.0;
+const GHOST_COLOR: Color = Color::rgba(0.8, 0.8, 1.0, 0.3);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct GhostFrame {
+    pub time: f32,
+    pub ball: [f32; 2],
+    pub crosshair: [f32; 2],
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub difficulty: GameDifficultyState,
+    pub score: i32,
+    pub frames: Vec<GhostFrame>,
+}
+
+impl GhostRun {
+    //interpolated ball and crosshair position, None once the run is over
+    fn sample(&self, time: f32) -> Option<(Vec2, Vec2)> {
+        let next = self.frames.partition_point(|frame| frame.time <= time);
+        let to = self.frames.get(next)?;
+        let Some(from) = next.checked_sub(1).map(|index| &self.frames[index]) else {
+            return Some((Vec2::from(to.ball), Vec2::from(to.crosshair)));
+        };
+
+        let t = (time - from.time) / (to.time - from.time);
+        Some((
+            Vec2::from(from.ball).lerp(Vec2::from(to.ball), t),
+            Vec2::from(from.crosshair).lerp(Vec2::from(to.crosshair), t),
+        ))
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GhostRecorder {
+    pub time: f32,
+    pub frames: Vec<GhostFrame>,
+    next_sample: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct GhostPlayback {
+    pub run: Option<GhostRun>,
+    pub time: f32,
+}
+
+#[derive(Component)]
+pub struct GhostBall;
+
+#[derive(Component)]
+pub struct GhostCrosshair;
+
+pub fn ghost_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    difficulty: Res<State<GameDifficultyState>>,
+    save_data: Res<SaveData>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut playback: ResMut<GhostPlayback>,
+) {
+    *recorder = GhostRecorder::default();
+    *playback = GhostPlayback::default();
+
+    //ghosts only race standard runs on the same difficulty
+    if *mode != GameMode::Standard || !rules.scored || save_data.hide_ghost {
+        return;
+    }
+
+    let Some(run) = save_data.ghost(*difficulty.get()) else {
+        return;
+    };
+
+    let ball = Ball::for_difficulty(*difficulty.get());
+
+    commands
+        .spawn(SpriteBundle {
+            texture: asset_server.load(ball.sprite()),
+            sprite: Sprite {
+                color: GHOST_COLOR,
+                custom_size: Some(Vec2::splat(ball.radius() * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -7.0),
+            ..default()
+        })
+        .insert(GhostBall)
+        .insert(InGameEntity);
+
+    commands
+        .spawn(SpriteBundle {
+            texture: asset_server.load("sprites/crosshair.png"),
+            sprite: Sprite {
+                color: GHOST_COLOR,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -1.0),
+            ..default()
+        })
+        .insert(GhostCrosshair)
+        .insert(InGameEntity);
+
+    info!("Racing ghost with score {}", run.score);
+    playback.run = Some(run.clone());
+}
+
+pub fn ghost_record(
+    time: Res<Time>,
+    mut recorder: ResMut<GhostRecorder>,
+    balls: Query<&Transform, With<Ball>>,
+    crosshairs: Query<(&Transform, &Player), With<CursorCrosshair>>,
+) {
+    recorder.time += time.delta_seconds();
+
+    if recorder.time < recorder.next_sample {
+        return;
+    }
+    recorder.next_sample = recorder.time + 1.0 / GHOST_SAMPLE_RATE;
+
+    //the highest ball stands in for split balls
+    let ball = balls
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .max_by(|a, b| a.y.total_cmp(&b.y))
+        .unwrap_or_default();
+    let crosshair = crosshairs
+        .iter()
+        .find(|(_, player)| **player == Player::One)
+        .map(|(transform, _)| transform.translation.truncate())
+        .unwrap_or_default();
+
+    let frame = GhostFrame {
+        time: recorder.time,
+        ball: ball.to_array(),
+        crosshair: crosshair.to_array(),
+    };
+    recorder.frames.push(frame);
+}
+
+pub fn ghost_playback(
+    time: Res<Time>,
+    mut playback: ResMut<GhostPlayback>,
+    mut ghost_balls: Query<
+        (&mut Transform, &mut Visibility),
+        (With<GhostBall>, Without<GhostCrosshair>),
+    >,
+    mut ghost_crosshairs: Query<(&mut Transform, &mut Visibility), With<GhostCrosshair>>,
+) {
+    playback.time += time.delta_seconds();
+
+    let Some(run) = &playback.run else {
+        return;
+    };
+
+    let sample = run.sample(playback.time);
+
+    for (mut transform, mut visibility) in &mut ghost_balls {
+        place_ghost(
+            sample.map(|(ball, _)| ball),
+            &mut transform,
+            &mut visibility,
+        );
+    }
+    for (mut transform, mut visibility) in &mut ghost_crosshairs {
+        place_ghost(
+            sample.map(|(_, crosshair)| crosshair),
+            &mut transform,
+            &mut visibility,
+        );
+    }
+}
+
+fn place_ghost(position: Option<Vec2>, transform: &mut Transform, visibility: &mut Visibility) {
+    match position {
+        Some(position) => {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            *visibility = Visibility::Inherited;
+        }
+        //the best run already ended here
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
+//a new best on this difficulty replaces its ghost
+pub fn ghost_store(
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    scores: Res<Scores>,
+    difficulty: Res<State<GameDifficultyState>>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut save_data: ResMut<SaveData>,
+) {
+    //the ghost stays an unassisted run
+    if *mode != GameMode::Standard || !rules.scored || rules.assisted() {
+        return;
+    }
+
+    let difficulty = *difficulty.get();
+    let best = save_data.ghost(difficulty).map(|run| run.score);
+
+    if best.is_some_and(|best| best >= scores.current_score) {
+        return;
+    }
+
+    let run = GhostRun {
+        difficulty,
+        score: scores.current_score,
+        frames: std::mem::take(&mut recorder.frames),
+    };
+
+    save_data
+        .ghosts
+        .retain(|ghost| ghost.difficulty != difficulty);
+    save_data.ghosts.push(run);
+    save_data.store();
+
+    info!("New ghost saved for {:?}", difficulty);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run() -> GhostRun {
+        GhostRun {
+            difficulty: GameDifficultyState::Medium,
+            score: 3,
+            frames: vec![
+                GhostFrame {
+                    time: 1.0,
+                    ball: [0.0, 0.0],
+                    crosshair: [10.0, 10.0],
+                },
+                GhostFrame {
+                    time: 2.0,
+                    ball: [100.0, -50.0],
+                    crosshair: [20.0, 0.0],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn waits_on_the_first_frame() {
+        let expected = (Vec2::ZERO, Vec2::splat(10.0));
+
+        assert_eq!(run().sample(0.0), Some(expected));
+        assert_eq!(run().sample(0.5), Some(expected));
+    }
+
+    #[test]
+    fn interpolates_between_frames() {
+        assert_eq!(
+            run().sample(1.5),
+            Some((Vec2::new(50.0, -25.0), Vec2::new(15.0, 5.0)))
+        );
+        assert_eq!(
+            run().sample(1.25),
+            Some((Vec2::new(25.0, -12.5), Vec2::new(12.5, 7.5)))
+        );
+    }
+
+    #[test]
+    fn ends_with_the_last_frame() {
+        assert_eq!(run().sample(2.0), None);
+        assert_eq!(run().sample(10.0), None);
+    }
+
+    #[test]
+    fn empty_run_has_no_ghost() {
+        let run = GhostRun {
+            frames: Vec::new(),
+            ..run()
+        };
+
+        assert_eq!(run.sample(0.0), None);
+    }
+}
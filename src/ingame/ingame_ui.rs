@@ -1,45 +1,264 @@
 use bevy::prelude::*;
 
+use crate::ingame::BulletTime;
+use crate::ingame::GameMode;
 use crate::ingame::InGameEntity;
+use crate::ingame::LevelUpEvent;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
 use crate::ingame::Scores;
+use crate::ingame::Versus;
+use crate::localization::Localization;
 
 #[derive(Component)]
 pub struct ScoreText;
 
-pub fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//split-screen score over each half of the screen
+#[derive(Component)]
+pub struct PlayerScoreText(pub Player);
+
+#[derive(Component)]
+pub struct BulletTimeMeter;
+
+#[derive(Component)]
+pub struct LevelUpText {
+    pub lifetime: Timer,
+}
+
+const METER_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+const METER_ACTIVE_COLOR: Color = Color::rgb(0.35, 0.67, 0.89);
+
+pub fn ui_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    versus: Res<Versus>,
+) {
+    let font = localization.font(&asset_server);
+
+    if rules.split_screen {
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    height: Val::Percent(10.0),
+                    width: Val::Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(InGameEntity)
+            .with_children(|parent| {
+                for &player in rules.players() {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(50.0),
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                localization.text(player.text_key()),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 30.0,
+                                    color: Color::YELLOW,
+                                },
+                            ));
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "0",
+                                    TextStyle {
+                                        font: font.clone(),
+                                        font_size: 60.0,
+                                        color: Color::WHITE,
+                                    },
+                                ),
+                                PlayerScoreText(player),
+                            ));
+                        });
+                }
+            });
+    } else {
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    height: Val::Percent(10.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(InGameEntity)
+            .insert(ScoreText)
+            //score text
+            .with_children(|commands| {
+                commands.spawn((
+                    TextBundle {
+                        text: Text::from_section(
+                            "0",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 80.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        ..default()
+                    },
+                    ScoreText,
+                ));
+            });
+    }
+
+    //hot-seat players need to know whose run it is
+    if *mode == GameMode::HotSeat {
+        commands
+            .spawn(TextBundle::from_section(
+                localization.format("player_turn", &[&localization.text(versus.turn.text_key())]),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 40.0,
+                    color: Color::YELLOW,
+                },
+            ))
+            .insert(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.0),
+                left: Val::Px(20.0),
+                ..default()
+            })
+            .insert(InGameEntity);
+    }
+
+    //bullet time meter background
     commands
         .spawn(NodeBundle {
             style: Style {
-                height: Val::Percent(10.0),
-                width: Val::Percent(100.0),
-                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                left: Val::Px(20.0),
+                width: Val::Px(300.0),
+                height: Val::Px(20.0),
+                border: UiRect::all(Val::Px(3.0)),
                 ..default()
             },
+            border_color: BorderColor(Color::BLACK),
+            background_color: Color::rgba(0.15, 0.15, 0.15, 0.6).into(),
             ..default()
         })
         .insert(InGameEntity)
-        .insert(ScoreText)
-        //score text
-        .with_children(|commands| {
-            commands.spawn((
-                TextBundle {
-                    text: Text::from_section(
-                        "Score",
-                        TextStyle {
-                            font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                            font_size: 80.0,
-                            color: Color::WHITE,
-                        },
-                    ),
+        //bullet time meter fill
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: METER_COLOR.into(),
                     ..default()
                 },
-                ScoreText,
+                BulletTimeMeter,
             ));
         });
 }
 
-pub fn ui_update(mut texts: Query<&mut Text, With<ScoreText>>, scores: ResMut<Scores>) {
+pub fn ui_update(
+    mut texts: Query<&mut Text, With<ScoreText>>,
+    mut player_texts: Query<(&mut Text, &PlayerScoreText), Without<ScoreText>>,
+    scores: ResMut<Scores>,
+) {
     for mut text in &mut texts {
         text.sections[0].value = scores.current_score.to_string();
     }
+
+    for (mut text, PlayerScoreText(player)) in &mut player_texts {
+        text.sections[0].value = scores.player_score(*player).to_string();
+    }
+}
+
+pub fn bullet_time_ui_update(
+    mut meters: Query<(&mut Style, &mut BackgroundColor), With<BulletTimeMeter>>,
+    bullet_time: Res<BulletTime>,
+) {
+    for (mut style, mut color) in &mut meters {
+        style.width = Val::Percent(bullet_time.meter * 100.0);
+        *color = if bullet_time.active {
+            METER_ACTIVE_COLOR.into()
+        } else {
+            METER_COLOR.into()
+        };
+    }
+}
+
+pub fn level_up_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    mut level_up_event: EventReader<LevelUpEvent>,
+    old_texts: Query<Entity, With<LevelUpText>>,
+) {
+    for event in level_up_event.iter() {
+        //only show the newest level
+        for text_entity in &old_texts {
+            commands.entity(text_entity).despawn_recursive();
+        }
+
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(20.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(LevelUpText {
+                lifetime: Timer::from_seconds(1.5, TimerMode::Once),
+            })
+            .insert(InGameEntity)
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    localization.format("level", &[&(event.level + 1)]),
+                    TextStyle {
+                        font: localization.font(&asset_server),
+                        font_size: 60.0,
+                        color: Color::YELLOW,
+                    },
+                ));
+            });
+    }
+}
+
+pub fn level_up_ui_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut level_texts: Query<(Entity, &mut LevelUpText, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (text_entity, mut level_text, children) in &mut level_texts {
+        level_text.lifetime.tick(time.delta());
+
+        for &child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.sections[0]
+                    .style
+                    .color
+                    .set_a(level_text.lifetime.percent_left());
+            }
+        }
+
+        if level_text.lifetime.finished() {
+            commands.entity(text_entity).despawn_recursive();
+        }
+    }
 }
@@ -1,17 +1,49 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
+pub mod aim_assist;
 pub mod animations;
+pub mod ball_types;
+pub mod ball_warnings;
+pub mod bullet_time;
+pub mod camera_effects;
+pub mod daily;
+pub mod difficulty_ramp;
 pub mod gameplay;
+pub mod ghost;
 pub mod ingame_ui;
+pub mod particles;
+pub mod practice;
+pub mod rules;
+pub mod run_stats;
 pub mod spawn;
+pub mod sprite_animator;
+pub mod trajectory;
+pub mod versus;
+pub mod wall_impacts;
 
 use crate::AppState;
 use crate::GameDifficultyState;
+use aim_assist::*;
 use animations::*;
+use ball_types::*;
+use ball_warnings::*;
+use bullet_time::*;
+use camera_effects::*;
+use daily::*;
+use difficulty_ramp::*;
 use gameplay::*;
+use ghost::*;
 use ingame_ui::*;
+use particles::*;
+use practice::*;
+use rules::*;
+use run_stats::*;
 use spawn::*;
+use sprite_animator::*;
+use trajectory::*;
+use versus::*;
+use wall_impacts::*;
 
 #[derive(Resource)]
 pub struct Scores {
@@ -20,16 +52,40 @@ pub struct Scores {
     pub easy_hscore: i32,
     pub medium_hscore: i32,
     pub hard_hscore: i32,
+    //aim assisted runs don't compete with unassisted ones
+    pub easy_assisted_hscore: i32,
+    pub medium_assisted_hscore: i32,
+    pub hard_assisted_hscore: i32,
+    //only used while split-screen runs two playfields
+    pub player_two_score: i32,
 }
 
-// Create the animation component
-// Note: you may make the animation an asset instead of a component
-#[derive(Component, Deref)]
-pub struct Animation(benimator::Animation);
+impl Scores {
+    pub fn player_score(&self, player: Player) -> i32 {
+        match player {
+            Player::One => self.current_score,
+            Player::Two => self.player_two_score,
+        }
+    }
+
+    pub fn player_score_mut(&mut self, player: Player) -> &mut i32 {
+        match player {
+            Player::One => &mut self.current_score,
+            Player::Two => &mut self.player_two_score,
+        }
+    }
 
-// Create the player component
-#[derive(Default, Component, Deref, DerefMut)]
-pub struct AnimationState(benimator::State);
+    pub fn high_score_mut(&mut self, difficulty: GameDifficultyState, assisted: bool) -> &mut i32 {
+        match (difficulty, assisted) {
+            (GameDifficultyState::Easy, false) => &mut self.easy_hscore,
+            (GameDifficultyState::Medium, false) => &mut self.medium_hscore,
+            (GameDifficultyState::Hard, false) => &mut self.hard_hscore,
+            (GameDifficultyState::Easy, true) => &mut self.easy_assisted_hscore,
+            (GameDifficultyState::Medium, true) => &mut self.medium_assisted_hscore,
+            (GameDifficultyState::Hard, true) => &mut self.hard_assisted_hscore,
+        }
+    }
+}
 
 pub struct InGamePlugin;
 
@@ -40,34 +96,73 @@ impl Plugin for InGamePlugin {
             .add_event::<JumpBallEvent>()
             .add_event::<ContactAnimationEvent>()
             .add_event::<M4AnimationEvent>()
-            .insert_resource(PlayAnimation(false))
+            .add_event::<AnimationFinishedEvent>()
+            .add_event::<ShotFiredEvent>()
+            .add_event::<WallHitEvent>()
+            .add_event::<LevelUpEvent>()
+            .init_resource::<BulletTime>()
+            .init_resource::<DifficultyRamp>()
+            .init_resource::<BallSelection>()
+            .init_resource::<BallRotation>()
+            .init_resource::<GameMode>()
+            .init_resource::<RunRules>()
+            .init_resource::<GameRng>()
+            .init_resource::<DailyChallenge>()
+            .init_resource::<RunStats>()
+            .init_resource::<Tutorial>()
+            .init_resource::<Versus>()
+            .init_resource::<CameraEffects>()
+            .init_resource::<GhostRecorder>()
+            .init_resource::<GhostPlayback>()
             .insert_resource(Scores {
                 current_score: 0,
                 high_score: 0,
                 easy_hscore: 0,
                 medium_hscore: 0,
                 hard_hscore: 0,
+                easy_assisted_hscore: 0,
+                medium_assisted_hscore: 0,
+                hard_assisted_hscore: 0,
+                player_two_score: 0,
             })
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (versus_setup, run_rules_setup.after(versus_setup)),
+            )
             .add_systems(
                 OnEnter(AppState::InGame),
                 (
                     setup,
                     ui_setup,
+                    bullet_time_reset,
+                    ball_rotation_reset,
+                    run_stats_reset,
+                    tutorial_setup,
+                    split_screen_setup,
+                    ghost_setup,
+                    danger_vignette_setup,
+                    camera_effects_reset,
                     game_difficulty_easy.run_if(in_state(GameDifficultyState::Easy)),
                     game_difficulty_medium.run_if(in_state(GameDifficultyState::Medium)),
                     game_difficulty_hard.run_if(in_state(GameDifficultyState::Hard)),
-                ),
+                )
+                    .after(run_rules_setup),
             )
             .add_systems(
                 Update,
                 (
                     contact_spawn,
-                    contact_animation,
+                    sprite_animator.after(ball_contact_checker),
                     fire_spawn,
-                    fire_animation,
-                    bullet_case_despawn,
-                    m4_animation,
-                    cursor_position,
+                    bullet_case_fade,
+                    bullet_case_impact,
+                    aim_assist_release
+                        .before(gamepad_crosshair)
+                        .before(cursor_position),
+                    cursor_position.after(gamepad_crosshair),
+                    aim_assist
+                        .after(cursor_position)
+                        .before(ball_contact_checker),
                     ball_movement,
                     ball_contact_checker,
                     m4_firerate_timer,
@@ -76,6 +171,98 @@ impl Plugin for InGamePlugin {
                 )
                     .run_if(in_state(AppState::InGame)),
             )
-            .add_systems(OnExit(AppState::InGame), entity_despawner);
+            .add_systems(
+                Update,
+                (
+                    bullet_time_meter.after(ball_contact_checker),
+                    bullet_time_controller,
+                    bullet_time_ui_update,
+                    difficulty_ramp.after(ball_movement),
+                    level_up_ui.after(difficulty_ramp),
+                    level_up_ui_fade,
+                    ball_rotation.after(ball_movement),
+                    ball_split.after(ball_movement),
+                    ball_marker.after(ball_rotation).after(ball_split),
+                    erratic_ball,
+                    fallen_ball_despawner.before(gameover_controller),
+                    run_stats_tracker,
+                    gamepad_crosshair,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                split_screen_drop_checker
+                    .after(fallen_ball_despawner)
+                    .before(gameover_controller)
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_equals(GameMode::SplitScreen)),
+            )
+            .add_systems(
+                Update,
+                (
+                    camera_effects_trigger.after(bullet_time_meter),
+                    camera_shake.after(camera_effects_trigger),
+                    hit_stop
+                        .after(camera_effects_trigger)
+                        .after(bullet_time_controller),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    wall_contact_checker,
+                    effect_cleanup.after(sprite_animator),
+                    particle_update,
+                    particle_emitters.after(ball_trail_setup),
+                    ball_trail_setup,
+                    impact_sparks.after(ball_contact_checker),
+                    muzzle_flash.after(ball_contact_checker),
+                    wall_debris.after(wall_contact_checker),
+                    wall_hit_sound.after(wall_contact_checker),
+                    wall_flash.after(wall_contact_checker),
+                    bank_shot_window.after(ball_contact_checker),
+                    bank_shot_bonus
+                        .after(wall_contact_checker)
+                        .after(bank_shot_window),
+                    bank_shot_text_fade,
+                    offscreen_indicator_spawn,
+                    offscreen_indicator_update.after(offscreen_indicator_spawn),
+                    danger_warning,
+                    trajectory_preview.after(ball_movement),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (ghost_record, ghost_playback)
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_equals(GameMode::Standard)),
+            )
+            .add_systems(
+                Update,
+                (
+                    tutorial_progress,
+                    practice_respawn.after(fallen_ball_despawner),
+                    practice_exit,
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_equals(GameMode::Practice)),
+            )
+            .add_systems(
+                OnExit(AppState::InGame),
+                (
+                    entity_despawner,
+                    effect_despawner,
+                    versus_recorder,
+                    split_screen_restore,
+                    ghost_store,
+                    camera_effects_reset,
+                    daily_result_recorder,
+                    run_stats_recorder,
+                    bullet_time_restore,
+                ),
+            );
     }
 }
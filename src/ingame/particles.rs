@@ -0,0 +1,304 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::accessibility::Accessibility;
+use crate::ingame::Ball;
+use crate::ingame::BallBaseColor;
+use crate::ingame::BulletTime;
+use crate::ingame::ContactAnimationEvent;
+use crate::ingame::CursorCrosshair;
+use crate::ingame::InGameEntity;
+use crate::ingame::M4AnimationEvent;
+use crate::ingame::Player;
+use crate::ingame::WallHitEvent;
+
+//colors above 1.0 are picked up by the camera's bloom
+pub const IMPACT_SPARKS: ParticleSettings = ParticleSettings {
+    count: 24,
+    speed: 300.0..900.0,
+    direction: Vec2::Y,
+    spread: std::f32::consts::PI,
+    lifetime: 0.2..0.45,
+    gravity: -1500.0,
+    start_color: Color::rgb(6.0, 4.0, 1.0),
+    end_color: Color::rgba(2.0, 0.3, 0.0, 0.0),
+    start_size: 6.0,
+    end_size: 1.0,
+};
+
+pub const MUZZLE_FLASH: ParticleSettings = ParticleSettings {
+    count: 12,
+    speed: 200.0..600.0,
+    direction: Vec2::new(0.6, -0.8),
+    spread: 0.5,
+    lifetime: 0.05..0.15,
+    gravity: 0.0,
+    start_color: Color::rgb(8.0, 7.0, 2.0),
+    end_color: Color::rgba(3.0, 1.0, 0.0, 0.0),
+    start_size: 8.0,
+    end_size: 2.0,
+};
+
+pub const WALL_DEBRIS: ParticleSettings = ParticleSettings {
+    count: 16,
+    speed: 150.0..500.0,
+    direction: Vec2::Y,
+    spread: 1.2,
+    lifetime: 0.4..0.8,
+    gravity: -2000.0,
+    start_color: Color::rgb(1.2, 1.2, 1.4),
+    end_color: Color::rgba(0.5, 0.5, 0.6, 0.0),
+    start_size: 7.0,
+    end_size: 3.0,
+};
+
+//slow cloud that hangs around the impact
+pub const WALL_DUST: ParticleSettings = ParticleSettings {
+    count: 10,
+    speed: 20.0..120.0,
+    direction: Vec2::Y,
+    spread: std::f32::consts::PI,
+    lifetime: 0.5..1.0,
+    gravity: 40.0,
+    start_color: Color::rgba(0.8, 0.8, 0.85, 0.35),
+    end_color: Color::rgba(0.6, 0.6, 0.65, 0.0),
+    start_size: 20.0,
+    end_size: 50.0,
+};
+
+//trail color is replaced by the ball's base color
+pub const BALL_TRAIL: ParticleSettings = ParticleSettings {
+    count: 1,
+    speed: 0.0..20.0,
+    direction: Vec2::Y,
+    spread: std::f32::consts::PI,
+    lifetime: 0.25..0.4,
+    gravity: 0.0,
+    start_color: Color::WHITE,
+    end_color: Color::NONE,
+    start_size: 30.0,
+    end_size: 4.0,
+};
+//trail particles per second for each ball
+const BALL_TRAIL_RATE: f32 = 60.0;
+
+//fewer, dimmer particles for players sensitive to flashes
+const REDUCED_FLASH_COUNT: f32 = 0.5;
+const REDUCED_FLASH_BRIGHTNESS: f32 = 0.15;
+
+#[derive(Clone)]
+pub struct ParticleSettings {
+    pub count: u32,
+    pub speed: Range<f32>,
+    //particles leave within `spread` radians either side of the direction
+    pub direction: Vec2,
+    pub spread: f32,
+    pub lifetime: Range<f32>,
+    pub gravity: f32,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+#[derive(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    gravity: f32,
+    lifetime: Timer,
+    start_color: Color,
+    end_color: Color,
+    start_size: f32,
+    end_size: f32,
+}
+
+//keeps emitting particles wherever its entity goes
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub settings: ParticleSettings,
+    pub timer: Timer,
+}
+
+impl ParticleEmitter {
+    pub fn new(settings: ParticleSettings, rate: f32) -> Self {
+        ParticleEmitter {
+            settings,
+            timer: Timer::from_seconds(1.0 / rate, TimerMode::Repeating),
+        }
+    }
+}
+
+impl ParticleSettings {
+    pub fn reduced_flash(&self) -> ParticleSettings {
+        ParticleSettings {
+            count: ((self.count as f32 * REDUCED_FLASH_COUNT) as u32).max(1),
+            start_color: self.start_color * REDUCED_FLASH_BRIGHTNESS,
+            end_color: self.end_color * REDUCED_FLASH_BRIGHTNESS,
+            ..self.clone()
+        }
+    }
+}
+
+pub fn spawn_particles(commands: &mut Commands, settings: &ParticleSettings, position: Vec3) {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..settings.count {
+        let angle = rng.gen_range(-settings.spread..=settings.spread);
+        let direction = Vec2::from_angle(angle).rotate(settings.direction.normalize_or_zero());
+        let speed = if settings.speed.is_empty() {
+            settings.speed.start
+        } else {
+            rng.gen_range(settings.speed.clone())
+        };
+        let lifetime = rng.gen_range(settings.lifetime.clone());
+
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: settings.start_color,
+                    custom_size: Some(Vec2::splat(settings.start_size)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position),
+                ..default()
+            })
+            .insert(Particle {
+                velocity: direction * speed,
+                gravity: settings.gravity,
+                lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+                start_color: settings.start_color,
+                end_color: settings.end_color,
+                start_size: settings.start_size,
+                end_size: settings.end_size,
+            })
+            .insert(InGameEntity);
+    }
+}
+
+pub fn particle_update(
+    mut commands: Commands,
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = bullet_time.scaled(time.delta());
+
+    for (entity, mut particle, mut transform, mut sprite) in &mut particles {
+        particle.lifetime.tick(delta);
+
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let seconds = delta.as_secs_f32();
+        particle.velocity.y += particle.gravity * seconds;
+        transform.translation += (particle.velocity * seconds).extend(0.0);
+
+        //fade and shrink over the particle's life
+        let t = particle.lifetime.percent();
+        let start = particle.start_color.as_rgba_f32();
+        let end = particle.end_color.as_rgba_f32();
+        sprite.color = Color::rgba(
+            start[0] + (end[0] - start[0]) * t,
+            start[1] + (end[1] - start[1]) * t,
+            start[2] + (end[2] - start[2]) * t,
+            start[3] + (end[3] - start[3]) * t,
+        );
+        let size = particle.start_size + (particle.end_size - particle.start_size) * t;
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+}
+
+pub fn particle_emitters(
+    mut commands: Commands,
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    mut emitters: Query<(&mut ParticleEmitter, &GlobalTransform)>,
+) {
+    for (mut emitter, transform) in &mut emitters {
+        emitter.timer.tick(bullet_time.scaled(time.delta()));
+
+        //trails sit just behind whatever drags them
+        let position = transform.translation() - Vec3::Z * 0.5;
+
+        for _ in 0..emitter.timer.times_finished_this_tick() {
+            spawn_particles(&mut commands, &emitter.settings, position);
+        }
+    }
+}
+
+pub fn ball_trail_setup(
+    mut commands: Commands,
+    balls: Query<(Entity, &Ball, &BallBaseColor), Added<Ball>>,
+) {
+    for (entity, ball, base_color) in &balls {
+        let color = base_color.0;
+        let settings = ParticleSettings {
+            start_color: color.with_a(0.5),
+            end_color: color.with_a(0.0),
+            start_size: ball.radius() * 0.6,
+            ..BALL_TRAIL
+        };
+
+        commands
+            .entity(entity)
+            .insert(ParticleEmitter::new(settings, BALL_TRAIL_RATE));
+    }
+}
+
+pub fn impact_sparks(
+    mut commands: Commands,
+    accessibility: Res<Accessibility>,
+    mut contact_event_reader: EventReader<ContactAnimationEvent>,
+) {
+    let settings = if accessibility.reduced_flash {
+        IMPACT_SPARKS.reduced_flash()
+    } else {
+        IMPACT_SPARKS
+    };
+
+    for event in contact_event_reader.iter() {
+        spawn_particles(&mut commands, &settings, event.position.extend(-1.5));
+    }
+}
+
+pub fn muzzle_flash(
+    mut commands: Commands,
+    accessibility: Res<Accessibility>,
+    crosshairs: Query<(&Transform, &Player), With<CursorCrosshair>>,
+    mut m4_event_reader: EventReader<M4AnimationEvent>,
+) {
+    let settings = if accessibility.reduced_flash {
+        MUZZLE_FLASH.reduced_flash()
+    } else {
+        MUZZLE_FLASH
+    };
+
+    for event in m4_event_reader.iter() {
+        let Some((crosshair, _)) = crosshairs
+            .iter()
+            .find(|(_, player)| **player == event.player)
+        else {
+            continue;
+        };
+
+        //same spot the fire sheet is drawn at
+        let position = crosshair.translation + Vec3::new(150.0, -100.0, 0.0);
+        spawn_particles(&mut commands, &settings, position.truncate().extend(-0.5));
+    }
+}
+
+pub fn wall_debris(mut commands: Commands, mut wall_hit_reader: EventReader<WallHitEvent>) {
+    for event in wall_hit_reader.iter() {
+        //debris flies back into the playfield
+        let settings = ParticleSettings {
+            direction: Vec2::new(event.normal.x, 0.5),
+            ..WALL_DEBRIS
+        };
+        spawn_particles(&mut commands, &settings, event.position.extend(-5.0));
+        spawn_particles(&mut commands, &WALL_DUST, event.position.extend(-5.5));
+    }
+}
@@ -0,0 +1,183 @@
+#![allow(clippy::too_many_arguments)]
+
+use bevy::{input::mouse::MouseMotion, prelude::*, window::CursorGrabMode};
+use bevy_rapier2d::prelude::*;
+
+use crate::ingame::Ball;
+use crate::ingame::GameMode;
+use crate::ingame::InGameEntity;
+use crate::ingame::JumpBallEvent;
+use crate::ingame::ShotFiredEvent;
+use crate::ingame::Wall;
+use crate::ingame::DEATH_LINE;
+use crate::localization::Localization;
+use crate::AppState;
+
+//practice balls fall slower than in a normal run
+pub const PRACTICE_GRAVITY_MULTIPLIER: f32 = 0.6;
+const AIM_DISTANCE: f32 = 400.0;
+const KEEP_IT_UP_HITS: u32 = 3;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TutorialStep {
+    #[default]
+    Aim,
+    Shoot,
+    KeepItUp,
+    Walls,
+    Done,
+}
+
+impl TutorialStep {
+    //translation key of the step's prompt
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            TutorialStep::Aim => "tutorial_aim",
+            TutorialStep::Shoot => "tutorial_shoot",
+            TutorialStep::KeepItUp => "tutorial_keep_it_up",
+            TutorialStep::Walls => "tutorial_walls",
+            TutorialStep::Done => "tutorial_done",
+        }
+    }
+
+    fn next(&self) -> TutorialStep {
+        match self {
+            TutorialStep::Aim => TutorialStep::Shoot,
+            TutorialStep::Shoot => TutorialStep::KeepItUp,
+            TutorialStep::KeepItUp => TutorialStep::Walls,
+            TutorialStep::Walls | TutorialStep::Done => TutorialStep::Done,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Tutorial {
+    pub step: TutorialStep,
+    pub aim_distance: f32,
+    pub hits: u32,
+}
+
+#[derive(Component)]
+pub struct TutorialText;
+
+pub fn tutorial_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    mode: Res<GameMode>,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    *tutorial = Tutorial::default();
+
+    if *mode != GameMode::Practice {
+        return;
+    }
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(110.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(InGameEntity)
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    localization.text(tutorial.step.text_key()),
+                    TextStyle {
+                        font: localization.font(&asset_server),
+                        font_size: 40.0,
+                        color: Color::YELLOW,
+                    },
+                ),
+                TutorialText,
+            ));
+        });
+}
+
+pub fn tutorial_progress(
+    rapier_context: Res<RapierContext>,
+    localization: Res<Localization>,
+    mut tutorial: ResMut<Tutorial>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut shot_event_reader: EventReader<ShotFiredEvent>,
+    mut jump_event_reader: EventReader<JumpBallEvent>,
+    balls: Query<Entity, With<Ball>>,
+    walls: Query<Entity, With<Wall>>,
+    mut texts: Query<&mut Text, With<TutorialText>>,
+) {
+    let moved: f32 = mouse_motion
+        .iter()
+        .map(|motion| motion.delta.length())
+        .sum();
+    let shots = shot_event_reader.iter().count();
+    let hits = jump_event_reader.iter().count() as u32;
+
+    let step_done = match tutorial.step {
+        TutorialStep::Aim => {
+            tutorial.aim_distance += moved;
+            tutorial.aim_distance >= AIM_DISTANCE
+        }
+        TutorialStep::Shoot => shots > 0,
+        TutorialStep::KeepItUp => {
+            tutorial.hits += hits;
+            tutorial.hits >= KEEP_IT_UP_HITS
+        }
+        TutorialStep::Walls => balls.iter().any(|ball| {
+            walls.iter().any(|wall| {
+                rapier_context
+                    .contact_pair(ball, wall)
+                    .is_some_and(|contact| contact.has_any_active_contacts())
+            })
+        }),
+        TutorialStep::Done => false,
+    };
+
+    if step_done {
+        tutorial.step = tutorial.step.next();
+        info!("Tutorial step: {:?}", tutorial.step);
+
+        for mut text in &mut texts {
+            text.sections[0].value = localization.text(tutorial.step.text_key());
+        }
+    }
+}
+
+//dropping the ball in practice just puts it back in the middle
+pub fn practice_respawn(
+    mut balls: Query<(&mut Transform, &mut Velocity, Option<&mut Sleeping>), With<Ball>>,
+) {
+    for (mut transform, mut velocity, sleeping) in &mut balls {
+        if transform.translation.y < DEATH_LINE {
+            transform.translation.x = 0.0;
+            transform.translation.y = 0.0;
+            transform.rotation = Quat::IDENTITY;
+            *velocity = Velocity::zero();
+
+            //only the starting ball can be put to sleep, split balls come back awake
+            if let Some(mut sleeping) = sleeping {
+                sleeping.sleeping = true;
+            }
+        }
+    }
+}
+
+pub fn practice_exit(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    mut windows: Query<&mut Window>,
+) {
+    if input.just_pressed(KeyCode::Escape) {
+        //enable cursor
+        let mut window = windows.single_mut();
+        window.cursor.visible = true;
+        window.cursor.grab_mode = CursorGrabMode::None;
+
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
@@ -0,0 +1,213 @@
+#![allow(clippy::too_many_arguments)]
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::accessibility::AimAssist;
+use crate::ingame::ball_types::BallSelection;
+use crate::ingame::BallKind;
+use crate::ingame::DailyChallenge;
+use crate::ingame::Player;
+use crate::ingame::Versus;
+use crate::ingame::PRACTICE_GRAVITY_MULTIPLIER;
+use crate::lan::LanMatch;
+use crate::save::SaveData;
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Daily,
+    Practice,
+    HotSeat,
+    SplitScreen,
+    Coop,
+    Lan,
+}
+
+//rules of the current run, decided once when the run starts
+#[derive(Resource)]
+pub struct RunRules {
+    pub ball: BallSelection,
+    pub gravity_multiplier: f32,
+    pub bullet_time: bool,
+    //unscored runs don't touch high scores or the daily history
+    pub scored: bool,
+    //dropped balls come back instead of ending the run
+    pub respawn: bool,
+    //the ramp follows player one's score, so it's off when two play at once
+    pub ramp: bool,
+    pub split_screen: bool,
+    //player two shares player one's ball
+    pub coop: bool,
+    //bonus points for balls that hit a wall right after a shot
+    pub bank_shots: bool,
+    pub trajectory_preview: bool,
+    pub aim_assist: AimAssist,
+}
+
+impl RunRules {
+    //players with their own playfield this run
+    pub fn players(&self) -> &'static [Player] {
+        if self.split_screen {
+            &Player::ALL
+        } else {
+            &Player::ALL[..1]
+        }
+    }
+
+    //players with their own crosshair and m4
+    pub fn shooters(&self) -> &'static [Player] {
+        if self.split_screen || self.coop {
+            &Player::ALL
+        } else {
+            &Player::ALL[..1]
+        }
+    }
+
+    //assisted runs still count, but are recorded apart from the others
+    pub fn assisted(&self) -> bool {
+        self.aim_assist != AimAssist::Off
+    }
+
+    pub fn origin(&self, player: Player) -> Vec2 {
+        if self.split_screen {
+            player.origin()
+        } else {
+            Vec2::ZERO
+        }
+    }
+}
+
+impl Default for RunRules {
+    fn default() -> Self {
+        Self {
+            ball: BallSelection::default(),
+            gravity_multiplier: 1.0,
+            bullet_time: true,
+            scored: true,
+            respawn: false,
+            ramp: true,
+            split_screen: false,
+            coop: false,
+            bank_shots: false,
+            trajectory_preview: false,
+            aim_assist: AimAssist::Off,
+        }
+    }
+}
+
+//gameplay randomness goes through this so seeded runs play out the same
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+pub fn run_rules_setup(
+    mode: Res<GameMode>,
+    ball_selection: Res<BallSelection>,
+    daily: Res<DailyChallenge>,
+    save_data: Res<SaveData>,
+    versus: Res<Versus>,
+    lan_match: Res<LanMatch>,
+    mut rules: ResMut<RunRules>,
+    mut rng: ResMut<GameRng>,
+) {
+    match *mode {
+        GameMode::Standard => {
+            *rules = RunRules {
+                ball: *ball_selection,
+                ..default()
+            };
+            *rng = GameRng::default();
+        }
+        GameMode::Daily => {
+            //only the first attempt of the day counts
+            let scored = save_data.daily_result(&daily.date).is_none();
+
+            *rules = RunRules {
+                ball: daily.ball,
+                gravity_multiplier: daily.gravity_multiplier,
+                bullet_time: daily.bullet_time,
+                scored,
+                ..default()
+            };
+            *rng = GameRng(StdRng::seed_from_u64(daily.seed));
+
+            info!("Daily challenge {} started, scored: {}", daily.date, scored);
+        }
+        GameMode::Practice => {
+            *rules = RunRules {
+                ball: *ball_selection,
+                gravity_multiplier: PRACTICE_GRAVITY_MULTIPLIER,
+                scored: false,
+                respawn: true,
+                ..default()
+            };
+            *rng = GameRng::default();
+        }
+        GameMode::HotSeat => {
+            *rules = RunRules {
+                ball: *ball_selection,
+                scored: false,
+                ..default()
+            };
+            *rng = GameRng(StdRng::seed_from_u64(versus.seed));
+        }
+        GameMode::SplitScreen => {
+            //rotation and bullet time would hit both playfields at once
+            let ball = match *ball_selection {
+                BallSelection::Rotating => BallSelection::Fixed(BallKind::Normal),
+                fixed => fixed,
+            };
+
+            *rules = RunRules {
+                ball,
+                bullet_time: false,
+                scored: false,
+                ramp: false,
+                split_screen: true,
+                ..default()
+            };
+            *rng = GameRng::default();
+        }
+        GameMode::Coop => {
+            //shared runs don't compete with solo high scores
+            *rules = RunRules {
+                ball: *ball_selection,
+                scored: false,
+                coop: true,
+                ..default()
+            };
+            *rng = GameRng::default();
+        }
+        GameMode::Lan => {
+            //both sides get the balls the relay's seed picks
+            *rules = RunRules {
+                ball: lan_match.0.ball,
+                scored: false,
+                ..default()
+            };
+            *rng = GameRng(StdRng::seed_from_u64(lan_match.0.seed));
+        }
+    }
+
+    //optional bonus, versus compares plain hits
+    rules.bank_shots = save_data.bank_shots && !rules.split_screen;
+
+    //seeing the arc ahead makes the run unranked
+    if save_data.trajectory_preview {
+        rules.trajectory_preview = true;
+        rules.scored = false;
+        info!("Trajectory preview on, run is unranked");
+    }
+
+    rules.aim_assist = save_data.accessibility.aim_assist;
+    if rules.assisted() {
+        info!("Aim assist {:?} on", rules.aim_assist);
+    }
+}
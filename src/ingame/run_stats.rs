@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ingame::GameMode;
+use crate::ingame::JumpBallEvent;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::ingame::ShotFiredEvent;
+use crate::save::SaveData;
+use crate::GameDifficultyState;
+
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub shots_fired: u32,
+    pub hits: u32,
+    pub kick_ups: u32,
+    pub run_time: f32,
+    //what each co-op player contributed
+    pub player_shots: [u32; 2],
+    pub player_hits: [u32; 2],
+}
+
+impl RunStats {
+    pub fn accuracy(&self) -> f32 {
+        accuracy(self.hits, self.shots_fired)
+    }
+
+    pub fn player_accuracy(&self, player: Player) -> f32 {
+        accuracy(
+            self.player_hits[player.index()],
+            self.player_shots[player.index()],
+        )
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub games_played: u32,
+    pub shots_fired: u32,
+    pub hits: u32,
+    pub longest_run_time: f32,
+    pub best_score: i32,
+    //best of the runs played with aim assist
+    pub assisted_best_score: i32,
+    pub easy_kick_ups: u32,
+    pub medium_kick_ups: u32,
+    pub hard_kick_ups: u32,
+}
+
+impl LifetimeStats {
+    pub fn accuracy(&self) -> f32 {
+        accuracy(self.hits, self.shots_fired)
+    }
+}
+
+fn accuracy(hits: u32, shots_fired: u32) -> f32 {
+    if shots_fired == 0 {
+        0.0
+    } else {
+        hits as f32 / shots_fired as f32 * 100.0
+    }
+}
+
+//seconds as m:ss for the stats texts
+pub fn format_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn run_stats_reset(mut run_stats: ResMut<RunStats>) {
+    *run_stats = RunStats::default();
+}
+
+pub fn run_stats_tracker(
+    time: Res<Time>,
+    mut run_stats: ResMut<RunStats>,
+    mut shot_event_reader: EventReader<ShotFiredEvent>,
+    mut jump_event_reader: EventReader<JumpBallEvent>,
+) {
+    run_stats.run_time += time.delta_seconds();
+
+    for shot in shot_event_reader.iter() {
+        run_stats.shots_fired += 1;
+        run_stats.player_shots[shot.player.index()] += 1;
+
+        if shot.hit {
+            run_stats.hits += 1;
+            run_stats.player_hits[shot.player.index()] += 1;
+        }
+    }
+
+    run_stats.kick_ups += jump_event_reader.iter().count() as u32;
+}
+
+pub fn run_stats_recorder(
+    run_stats: Res<RunStats>,
+    scores: Res<Scores>,
+    mode: Res<GameMode>,
+    rules: Res<RunRules>,
+    difficulty: Res<State<GameDifficultyState>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    //practice sessions aren't real games
+    if *mode == GameMode::Practice {
+        return;
+    }
+
+    let lifetime = &mut save_data.lifetime_stats;
+
+    lifetime.games_played += 1;
+    lifetime.shots_fired += run_stats.shots_fired;
+    lifetime.hits += run_stats.hits;
+    lifetime.longest_run_time = lifetime.longest_run_time.max(run_stats.run_time);
+
+    //unranked runs still count as played, but don't raise the best
+    if rules.scored && rules.assisted() {
+        lifetime.assisted_best_score = lifetime.assisted_best_score.max(scores.current_score);
+    } else if rules.scored {
+        lifetime.best_score = lifetime.best_score.max(scores.current_score);
+    }
+
+    match difficulty.get() {
+        GameDifficultyState::Easy => lifetime.easy_kick_ups += run_stats.kick_ups,
+        GameDifficultyState::Medium => lifetime.medium_kick_ups += run_stats.kick_ups,
+        GameDifficultyState::Hard => lifetime.hard_kick_ups += run_stats.kick_ups,
+    }
+
+    save_data.store();
+}
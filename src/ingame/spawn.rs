@@ -1,13 +1,28 @@
+#![allow(clippy::too_many_arguments)]
+
 use bevy::{prelude::*, window::CursorGrabMode};
 use bevy_kira_audio::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::ingame::Animation;
-use crate::ingame::AnimationState;
+use crate::accessibility::Accessibility;
+use crate::cosmetics::Loadout;
+use crate::ingame::AimPull;
+use crate::ingame::BallKind;
+use crate::ingame::DifficultyRamp;
+use crate::ingame::GameRng;
+use crate::ingame::PlaybackMode;
+use crate::ingame::Player;
+use crate::ingame::RampCurve;
+use crate::ingame::RunRules;
 use crate::ingame::Scores;
+use crate::ingame::SpriteAnimator;
+use crate::GameDifficultyState;
 use crate::SCREEN_HEIGHT;
 use crate::SCREEN_WIDTH;
 
+const COOP_CROSSHAIR_COLOR: Color = Color::rgb(1.0, 0.6, 0.2);
+const HIGH_CONTRAST_CROSSHAIR_COLOR: Color = Color::rgb(1.0, 1.0, 0.0);
+
 #[derive(Component)]
 pub struct InGameEntity;
 
@@ -15,18 +30,81 @@ pub struct InGameEntity;
 pub struct CursorCrosshair;
 
 #[derive(Component)]
+pub struct Wall;
+
+//only catches bullet casings, balls fall through it
+#[derive(Component)]
+pub struct Floor;
+
+//balls and casings never touch, everything else keeps colliding with both
+pub const BALL_GROUP: Group = Group::GROUP_1;
+pub const CASING_GROUP: Group = Group::GROUP_2;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Ball {
     Easy,
     Medium,
     Hard,
 }
 
+impl Ball {
+    pub fn for_difficulty(difficulty: GameDifficultyState) -> Ball {
+        match difficulty {
+            GameDifficultyState::Easy => Ball::Easy,
+            GameDifficultyState::Medium => Ball::Medium,
+            GameDifficultyState::Hard => Ball::Hard,
+        }
+    }
+
+    pub fn sprite(&self) -> &'static str {
+        match self {
+            Ball::Easy => "sprites/easy_ball.png",
+            Ball::Medium => "sprites/medium_ball.png",
+            Ball::Hard => "sprites/hard_ball.png",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Ball::Easy => Color::rgb(0.40, 1.5, 0.8),
+            Ball::Medium => Color::rgb(0.7, 1.4, 1.8),
+            Ball::Hard => Color::rgb(1.8, 0.40, 0.40),
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        match self {
+            Ball::Easy | Ball::Medium => 50.0,
+            Ball::Hard => 25.0,
+        }
+    }
+
+    pub fn density(&self) -> f32 {
+        match self {
+            Ball::Easy | Ball::Medium => 0.1,
+            Ball::Hard => 0.4,
+        }
+    }
+
+    pub fn ramp_curve(&self) -> RampCurve {
+        match self {
+            Ball::Easy => RampCurve::EASY,
+            Ball::Medium => RampCurve::MEDIUM,
+            Ball::Hard => RampCurve::HARD,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct M4 {
     pub lifetime: Timer,
     pub okay_to_shoot: bool,
 }
 
+//ball color without ball kind tint, from the difficulty or the equipped skin
+#[derive(Component, Clone, Copy)]
+pub struct BallBaseColor(pub Color);
+
 #[derive(Component)]
 pub struct EndGameTimer {
     pub lifetime: Timer,
@@ -39,10 +117,17 @@ pub fn setup(
     mut windows: Query<&mut Window>,
     mut scores: ResMut<Scores>,
     mut textures: ResMut<Assets<TextureAtlas>>,
+    loadout: Res<Loadout>,
+    accessibility: Res<Accessibility>,
+    rules: Res<RunRules>,
 ) {
     info!("Game Started");
 
+    let crosshair_skin = loadout.crosshair();
+    let crosshair_size = crosshair_skin.size * accessibility.crosshair_scale;
+
     scores.current_score = 0;
+    scores.player_two_score = 0;
 
     audio.play(asset_server.load("sounds/start.ogg"));
 
@@ -58,209 +143,306 @@ pub fn setup(
         })
         .insert(InGameEntity);
 
-    //background spawn
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/background.png"),
-            transform: Transform::from_xyz(0.0, 0.0, -9.0),
-            ..default()
-        })
-        .insert(InGameEntity);
+    //every player gets their own playfield
+    for &player in rules.players() {
+        let origin = rules.origin(player);
 
-    //spawn m4 with animation props
-    commands
-        // Spawn a bevy sprite-sheet
-        .spawn(SpriteSheetBundle {
-            texture_atlas: textures.add(TextureAtlas::from_grid(
-                asset_server.load("sprites/m4_sheet.png"),
-                Vec2::new(1550.0, 720.0),
-                5,
-                1,
-                None,
-                None,
-            )),
-            ..default()
-        })
-        //Create and insert an animation
-        .insert(Animation(benimator::Animation::from_indices(
-            0..=4,
-            benimator::FrameRate::from_fps(30.0),
-        )))
-        // Insert the state
-        .insert(AnimationState::default())
-        .insert(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 0.0)))
-        .insert(M4 {
-            lifetime: Timer::from_seconds(0.2, TimerMode::Once),
-            okay_to_shoot: true,
-        })
-        .insert(InGameEntity);
+        //background spawn
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("sprites/background.png"),
+                transform: Transform::from_xyz(origin.x, origin.y, -9.0),
+                ..default()
+            })
+            .insert(player)
+            .insert(InGameEntity);
 
-    //crosshair and collision spawn
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/crosshair.png"),
-            sprite: Sprite {
-                color: Color::rgb(1.0, 1.0, 1.0),
+        //spawn side walls
+        //right wall
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("sprites/wall.png"),
+                sprite: Sprite {
+                    color: Color::rgba(5.0, 5.0, 5.0, 0.1),
+                    flip_x: true,
+                    ..default()
+                },
                 ..default()
-            },
-            ..default()
-        })
-        .insert(Collider::ball(5.0))
-        .insert(Sensor)
-        .insert(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 0.0)))
-        .insert(ActiveEvents::COLLISION_EVENTS)
-        .insert(CursorCrosshair)
-        .insert(InGameEntity);
+            })
+            .insert(Collider::cuboid(100.0, SCREEN_HEIGHT / 2.0 + 500.0))
+            .insert(TransformBundle::from(Transform::from_xyz(
+                origin.x + SCREEN_WIDTH / 2.0,
+                origin.y,
+                -6.0,
+            )))
+            .insert(Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            })
+            .insert(Wall)
+            .insert(player)
+            .insert(InGameEntity);
 
-    //spawn side walls
-    //right wall
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/wall.png"),
-            sprite: Sprite {
-                color: Color::rgba(5.0, 5.0, 5.0, 0.1),
-                flip_x: true,
+        //left wall
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("sprites/wall.png"),
+                sprite: Sprite {
+                    color: Color::rgba(5.0, 5.0, 5.0, 0.1),
+                    ..default()
+                },
                 ..default()
-            },
-            ..default()
-        })
-        .insert(Collider::cuboid(100.0, SCREEN_HEIGHT / 2.0 + 500.0))
-        .insert(TransformBundle::from(Transform::from_xyz(
-            SCREEN_WIDTH / 2.0,
-            0.0,
-            -6.0,
-        )))
-        .insert(Friction {
-            coefficient: 0.0,
-            combine_rule: CoefficientCombineRule::Min,
-        })
-        .insert(InGameEntity);
+            })
+            .insert(Collider::cuboid(100.0, SCREEN_HEIGHT / 2.0 + 500.0))
+            .insert(TransformBundle::from(Transform::from_xyz(
+                origin.x - SCREEN_WIDTH / 2.0,
+                origin.y,
+                -6.0,
+            )))
+            .insert(Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            })
+            .insert(Wall)
+            .insert(player)
+            .insert(InGameEntity);
 
-    //left wall
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/wall.png"),
-            sprite: Sprite {
-                color: Color::rgba(5.0, 5.0, 5.0, 0.1),
+        //floor for the casings, just above the bottom edge
+        commands
+            .spawn(Collider::cuboid(SCREEN_WIDTH / 2.0, 50.0))
+            .insert(TransformBundle::from(Transform::from_xyz(
+                origin.x,
+                origin.y - SCREEN_HEIGHT / 2.0 - 30.0,
+                0.0,
+            )))
+            .insert(CollisionGroups::new(CASING_GROUP, CASING_GROUP))
+            .insert(Floor)
+            .insert(player)
+            .insert(InGameEntity);
+    }
+
+    //co-op puts both shooters on the same playfield
+    for &player in rules.shooters() {
+        let origin = rules.origin(player);
+
+        //spawn m4 with animation props
+        commands
+            // Spawn a bevy sprite-sheet
+            .spawn(SpriteSheetBundle {
+                texture_atlas: textures.add(TextureAtlas::from_grid(
+                    asset_server.load("sprites/m4_sheet.png"),
+                    Vec2::new(1550.0, 720.0),
+                    5,
+                    1,
+                    None,
+                    None,
+                )),
+                sprite: TextureAtlasSprite {
+                    color: loadout.camo().tint,
+                    ..default()
+                },
                 ..default()
-            },
-            ..default()
-        })
-        .insert(Collider::cuboid(100.0, SCREEN_HEIGHT / 2.0 + 500.0))
-        .insert(TransformBundle::from(Transform::from_xyz(
-            -SCREEN_WIDTH / 2.0,
-            0.0,
-            -6.0,
-        )))
-        .insert(Friction {
-            coefficient: 0.0,
-            combine_rule: CoefficientCombineRule::Min,
-        })
-        .insert(InGameEntity);
+            })
+            .insert(SpriteAnimator::new().with_clip("fire", 0..=4, 30.0, PlaybackMode::Once))
+            .insert(TransformBundle::from(Transform::from_xyz(
+                origin.x, origin.y, 0.0,
+            )))
+            .insert(M4 {
+                lifetime: Timer::from_seconds(0.2, TimerMode::Once),
+                okay_to_shoot: true,
+            })
+            .insert(player)
+            .insert(InGameEntity);
+
+        //crosshair and collision spawn
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("sprites/crosshair.png"),
+                sprite: Sprite {
+                    //tell the co-op crosshairs apart
+                    color: if rules.coop && player == Player::Two {
+                        COOP_CROSSHAIR_COLOR
+                    } else if accessibility.high_contrast_crosshair {
+                        HIGH_CONTRAST_CROSSHAIR_COLOR
+                    } else {
+                        crosshair_skin.color
+                    },
+                    custom_size: Some(Vec2::splat(crosshair_size)),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(Collider::ball(rules.aim_assist.sensor_radius()))
+            .insert(Sensor)
+            .insert(TransformBundle::from(Transform::from_xyz(
+                origin.x, origin.y, 0.0,
+            )))
+            .insert(ActiveEvents::COLLISION_EVENTS)
+            .insert(CursorCrosshair)
+            .insert(AimPull::default())
+            .insert(player)
+            .insert(InGameEntity)
+            //dark outline keeps the crosshair readable on any background
+            .with_children(|parent| {
+                if !accessibility.high_contrast_crosshair {
+                    return;
+                }
+
+                parent.spawn(SpriteBundle {
+                    texture: asset_server.load("sprites/crosshair.png"),
+                    sprite: Sprite {
+                        color: Color::BLACK,
+                        custom_size: Some(Vec2::splat(crosshair_size * 1.5)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                    ..default()
+                });
+            });
+    }
 }
 
-pub fn game_difficulty_easy(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn game_difficulty_easy(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rules: Res<RunRules>,
+    loadout: Res<Loadout>,
+    accessibility: Res<Accessibility>,
+    mut rng: ResMut<GameRng>,
+) {
     info!("Easy ball created");
 
-    //normal jump-ball spawn
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/easy_ball.png"),
-            sprite: Sprite {
-                color: Color::rgb(0.40, 1.5, 0.8),
-                ..default()
-            },
-            ..default()
-        })
-        .insert(Collider::ball(50.0))
-        .insert(Sleeping {
-            sleeping: true,
-            ..default()
-        })
-        .insert(TransformBundle::from(Transform::from_xyz(0.0, 0.0, -6.0)))
-        .insert(RigidBody::Dynamic)
-        .insert(GravityScale(17.0))
-        .insert(ColliderMassProperties::Density(0.1))
-        .insert(Restitution {
-            coefficient: 1.0,
-            combine_rule: CoefficientCombineRule::Average,
-        })
-        .insert(Velocity {
-            linvel: Vec2::new(0.0, 0.0),
-            angvel: 0.0,
-        })
-        .insert(ExternalImpulse {
-            impulse: Vec2::new(0.0, 0.0),
-            torque_impulse: 0.0,
-        })
-        .insert(Ball::Easy)
-        .insert(InGameEntity);
+    spawn_starting_ball(
+        &mut commands,
+        &asset_server,
+        Ball::Easy,
+        &rules,
+        &loadout,
+        &accessibility,
+        &mut rng,
+    );
 }
 
-pub fn game_difficulty_medium(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn game_difficulty_medium(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rules: Res<RunRules>,
+    loadout: Res<Loadout>,
+    accessibility: Res<Accessibility>,
+    mut rng: ResMut<GameRng>,
+) {
     info!("Medium ball created");
 
-    //normal jump-ball spawn
-    commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/medium_ball.png"),
-            sprite: Sprite {
-                color: Color::rgb(0.7, 1.4, 1.8),
-                ..default()
-            },
-            ..default()
-        })
-        .insert(Collider::ball(50.0))
-        .insert(Sleeping {
-            sleeping: true,
-            ..default()
-        })
-        .insert(TransformBundle::from(Transform::from_xyz(0.0, 0.0, -6.0)))
-        .insert(RigidBody::Dynamic)
-        .insert(GravityScale(30.0))
-        .insert(ColliderMassProperties::Density(0.1))
-        .insert(Restitution {
-            coefficient: 1.0,
-            combine_rule: CoefficientCombineRule::Average,
-        })
-        .insert(Velocity {
-            linvel: Vec2::new(0.0, 0.0),
-            angvel: 0.0,
-        })
-        .insert(ExternalImpulse {
-            impulse: Vec2::new(0.0, 0.0),
-            torque_impulse: 0.0,
-        })
-        .insert(Ball::Medium)
-        .insert(InGameEntity);
+    spawn_starting_ball(
+        &mut commands,
+        &asset_server,
+        Ball::Medium,
+        &rules,
+        &loadout,
+        &accessibility,
+        &mut rng,
+    );
 }
 
-pub fn game_difficulty_hard(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn game_difficulty_hard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rules: Res<RunRules>,
+    loadout: Res<Loadout>,
+    accessibility: Res<Accessibility>,
+    mut rng: ResMut<GameRng>,
+) {
     info!("Hard ball created");
 
-    //normal jump-ball spawn
+    spawn_starting_ball(
+        &mut commands,
+        &asset_server,
+        Ball::Hard,
+        &rules,
+        &loadout,
+        &accessibility,
+        &mut rng,
+    );
+}
+
+fn spawn_starting_ball(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    ball: Ball,
+    rules: &RunRules,
+    loadout: &Loadout,
+    accessibility: &Accessibility,
+    rng: &mut GameRng,
+) {
+    let mut ramp = DifficultyRamp::new(ball.ramp_curve());
+    ramp.gravity_multiplier = rules.gravity_multiplier;
+    let kind = rules.ball.starting_kind(&mut rng.0);
+    let skin = loadout.ball_skin();
+
+    for &player in rules.players() {
+        let origin = rules.origin(player);
+
+        //ball waits in the middle until the first shot
+        let ball_entity = spawn_ball(
+            commands,
+            ball,
+            kind,
+            &ramp,
+            Transform::from_xyz(origin.x, origin.y, -6.0),
+            asset_server.load(skin.sprite.unwrap_or(ball.sprite())),
+            BallBaseColor(skin.tint.unwrap_or(accessibility.palette.ball_color(ball))),
+        );
+        commands
+            .entity(ball_entity)
+            .insert(player)
+            .insert(Sleeping {
+                sleeping: true,
+                ..default()
+            });
+    }
+
+    commands.insert_resource(ramp);
+}
+
+//normal jump-ball spawn
+pub fn spawn_ball(
+    commands: &mut Commands,
+    ball: Ball,
+    kind: BallKind,
+    ramp: &DifficultyRamp,
+    transform: Transform,
+    texture: Handle<Image>,
+    base_color: BallBaseColor,
+) -> Entity {
+    let profile = kind.profile();
+
     commands
         .spawn(SpriteBundle {
-            texture: asset_server.load("sprites/hard_ball.png"),
+            texture,
             sprite: Sprite {
-                color: Color::rgb(1.8, 0.40, 0.40),
+                color: profile.tint.unwrap_or(base_color.0),
+                //skins may use a sprite made for another ball size
+                custom_size: Some(Vec2::splat(ball.radius() * 2.0)),
                 ..default()
             },
             ..default()
         })
-        .insert(Collider::ball(25.0))
-        .insert(Sleeping {
-            sleeping: true,
-            ..default()
-        })
-        .insert(TransformBundle::from(Transform::from_xyz(0.0, 0.0, -6.0)))
+        .insert(Collider::ball(ball.radius()))
+        .insert(CollisionGroups::new(BALL_GROUP, Group::ALL))
+        .insert(TransformBundle::from(transform))
         .insert(RigidBody::Dynamic)
-        .insert(GravityScale(24.0))
-        .insert(ColliderMassProperties::Density(0.4))
+        .insert(GravityScale(ramp.gravity() * profile.gravity_multiplier))
+        .insert(ColliderMassProperties::Density(
+            ball.density() * profile.density_multiplier,
+        ))
         .insert(Restitution {
-            coefficient: 1.0,
+            coefficient: profile.restitution,
             combine_rule: CoefficientCombineRule::Average,
         })
+        .insert(Damping {
+            linear_damping: profile.linear_damping,
+            angular_damping: 0.0,
+        })
         .insert(Velocity {
             linvel: Vec2::new(0.0, 0.0),
             angvel: 0.0,
@@ -269,6 +451,9 @@ pub fn game_difficulty_hard(mut commands: Commands, asset_server: Res<AssetServe
             impulse: Vec2::new(0.0, 0.0),
             torque_impulse: 0.0,
         })
-        .insert(Ball::Hard)
-        .insert(InGameEntity);
+        .insert(ball)
+        .insert(kind)
+        .insert(base_color)
+        .insert(InGameEntity)
+        .id()
 }
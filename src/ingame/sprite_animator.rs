@@ -0,0 +1,126 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::ingame::BulletTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackMode {
+    Once,
+    Loop,
+    PingPong,
+}
+
+//named sprite sheet clips, any entity with a texture atlas can play them
+#[derive(Component, Default)]
+pub struct SpriteAnimator {
+    clips: HashMap<&'static str, benimator::Animation>,
+    current: Option<&'static str>,
+    state: benimator::State,
+    playing: bool,
+    finished: bool,
+    pub speed: f32,
+}
+
+impl SpriteAnimator {
+    pub fn new() -> Self {
+        SpriteAnimator {
+            speed: 1.0,
+            ..default()
+        }
+    }
+
+    pub fn with_clip(
+        mut self,
+        name: &'static str,
+        frames: impl IntoIterator<Item = usize>,
+        fps: f64,
+        mode: PlaybackMode,
+    ) -> Self {
+        let animation =
+            benimator::Animation::from_indices(frames, benimator::FrameRate::from_fps(fps));
+        let animation = match mode {
+            PlaybackMode::Once => animation.once(),
+            PlaybackMode::Loop => animation.repeat(),
+            PlaybackMode::PingPong => animation.ping_pong(),
+        };
+
+        self.clips.insert(name, animation);
+        self
+    }
+
+    //start playing right after spawning
+    pub fn autoplay(mut self, name: &'static str) -> Self {
+        self.play(name);
+        self
+    }
+
+    //restarts the clip even if it's already playing
+    pub fn play(&mut self, name: &'static str) {
+        if !self.clips.contains_key(name) {
+            warn!("Animation clip {} doesn't exist", name);
+            return;
+        }
+
+        self.current = Some(name);
+        self.state.reset();
+        self.playing = true;
+        self.finished = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.playing = self.current.is_some() && !self.finished;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    //only once clips ever finish
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn current_clip(&self) -> Option<&'static str> {
+        self.current
+    }
+}
+
+#[derive(Event)]
+pub struct AnimationFinishedEvent {
+    pub entity: Entity,
+    pub clip: &'static str,
+}
+
+pub fn sprite_animator(
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    mut animators: Query<(Entity, &mut SpriteAnimator, &mut TextureAtlasSprite)>,
+    mut finished_event_writer: EventWriter<AnimationFinishedEvent>,
+) {
+    let delta = bullet_time.scaled(time.delta());
+
+    for (entity, mut animator, mut texture) in &mut animators {
+        if !animator.playing {
+            continue;
+        }
+        let Some(clip) = animator.current else {
+            continue;
+        };
+
+        let animator = &mut *animator;
+        let animation = &animator.clips[clip];
+        animator
+            .state
+            .update(animation, delta.mul_f32(animator.speed));
+        texture.index = animator.state.frame_index();
+
+        if animator.state.is_ended() {
+            animator.playing = false;
+            animator.finished = true;
+            finished_event_writer.send(AnimationFinishedEvent { entity, clip });
+        }
+    }
+}
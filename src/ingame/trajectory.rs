@@ -0,0 +1,196 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::ingame::Ball;
+use crate::ingame::InGameEntity;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::DEATH_LINE;
+use crate::SCREEN_WIDTH;
+
+//how far ahead the arc is predicted, and how it's drawn
+const PREVIEW_SECONDS: f32 = 1.2;
+const DOTS_PER_BALL: usize = 30;
+const SUBSTEPS_PER_DOT: usize = 10;
+const DOT_SIZE: f32 = 8.0;
+const DOT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+//inner edge of the side walls, relative to the playfield's origin
+const WALL_INNER_X: f32 = SCREEN_WIDTH / 2.0 - 100.0;
+
+#[derive(Component)]
+pub struct TrajectoryDot;
+
+//step the ball forward the way rapier would, bouncing off the side walls
+fn predict_arc(
+    start: Vec2,
+    velocity: Vec2,
+    gravity: Vec2,
+    damping: f32,
+    bounce: f32,
+    radius: f32,
+    origin: Vec2,
+) -> Vec<Vec2> {
+    let dot_step = PREVIEW_SECONDS / DOTS_PER_BALL as f32;
+    let dt = dot_step / SUBSTEPS_PER_DOT as f32;
+    let left = origin.x - WALL_INNER_X + radius;
+    let right = origin.x + WALL_INNER_X - radius;
+
+    let mut position = start;
+    let mut velocity = velocity;
+    let mut dots = Vec::with_capacity(DOTS_PER_BALL);
+
+    for _ in 0..DOTS_PER_BALL {
+        for _ in 0..SUBSTEPS_PER_DOT {
+            velocity += gravity * dt;
+            velocity *= 1.0 / (1.0 + dt * damping);
+            position += velocity * dt;
+
+            if position.x < left || position.x > right {
+                position.x = position.x.clamp(left, right);
+                velocity.x = -velocity.x * bounce;
+            }
+        }
+
+        if position.y < DEATH_LINE {
+            break;
+        }
+        dots.push(position);
+    }
+
+    dots
+}
+
+pub fn trajectory_preview(
+    mut commands: Commands,
+    rules: Res<RunRules>,
+    rapier_config: Res<RapierConfiguration>,
+    balls: Query<
+        (
+            &Transform,
+            &Velocity,
+            &GravityScale,
+            &Damping,
+            &Restitution,
+            &Ball,
+            &Player,
+        ),
+        Without<TrajectoryDot>,
+    >,
+    mut dots: Query<(&mut Transform, &mut Visibility), With<TrajectoryDot>>,
+) {
+    if !rules.trajectory_preview {
+        return;
+    }
+
+    let mut positions = Vec::new();
+
+    for (transform, velocity, gravity_scale, damping, restitution, ball, player) in &balls {
+        //walls have no restitution, rapier averages the two
+        let bounce = restitution.coefficient / 2.0;
+
+        positions.extend(predict_arc(
+            transform.translation.truncate(),
+            velocity.linvel,
+            rapier_config.gravity * gravity_scale.0,
+            damping.linear_damping,
+            bounce,
+            ball.radius() * transform.scale.x,
+            rules.origin(*player),
+        ));
+    }
+
+    let mut unused = positions.iter();
+
+    for (mut transform, mut visibility) in &mut dots {
+        match unused.next() {
+            Some(position) => {
+                transform.translation = position.extend(-4.0);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    //more balls than dots, the new ones show up next frame
+    for _ in unused {
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: DOT_COLOR,
+                    custom_size: Some(Vec2::splat(DOT_SIZE)),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            })
+            .insert(TrajectoryDot)
+            .insert(InGameEntity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RADIUS: f32 = 20.0;
+
+    //a ball rolling sideways with nothing slowing it down
+    fn sideways(start: Vec2, speed: f32, bounce: f32, origin: Vec2) -> Vec<Vec2> {
+        predict_arc(
+            start,
+            Vec2::new(speed, 0.0),
+            Vec2::ZERO,
+            0.0,
+            bounce,
+            RADIUS,
+            origin,
+        )
+    }
+
+    #[test]
+    fn bounces_back_off_the_right_wall() {
+        let dots = sideways(Vec2::new(500.0, 0.0), 400.0, 1.0, Vec2::ZERO);
+        let right = WALL_INNER_X - RADIUS;
+
+        assert_eq!(dots.len(), DOTS_PER_BALL);
+        assert!(dots.iter().all(|dot| dot.x <= right));
+        assert!(dots.last().unwrap().x < dots[0].x);
+    }
+
+    #[test]
+    fn bounce_slows_the_ball_down() {
+        //20 to the wall, then half speed back for the rest of the preview
+        let dots = sideways(Vec2::new(500.0, 0.0), 400.0, 0.5, Vec2::ZERO);
+        let expected = 520.0 - 200.0 * (PREVIEW_SECONDS - 0.05);
+
+        assert!((dots.last().unwrap().x - expected).abs() < 2.0);
+    }
+
+    #[test]
+    fn walls_follow_the_playfield_origin() {
+        let origin = Vec2::new(SCREEN_WIDTH / 4.0, 0.0);
+        let left = origin.x - WALL_INNER_X + RADIUS;
+        let dots = sideways(origin - Vec2::new(500.0, 0.0), -400.0, 1.0, origin);
+
+        assert!(dots.iter().all(|dot| dot.x >= left));
+        assert!(dots.last().unwrap().x > dots[0].x);
+    }
+
+    #[test]
+    fn stops_at_the_death_line() {
+        let dots = predict_arc(
+            Vec2::ZERO,
+            Vec2::new(0.0, -1000.0),
+            Vec2::ZERO,
+            0.0,
+            1.0,
+            RADIUS,
+            Vec2::ZERO,
+        );
+
+        assert!(dots.len() < DOTS_PER_BALL);
+        assert!(dots.iter().all(|dot| dot.y >= DEATH_LINE));
+    }
+}
@@ -0,0 +1,342 @@
+#![allow(clippy::too_many_arguments)]
+
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
+    prelude::*,
+    render::camera::Viewport,
+};
+use rand::Rng;
+
+use crate::ingame::Ball;
+use crate::ingame::CursorCrosshair;
+use crate::ingame::GameMode;
+use crate::ingame::InGameEntity;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::ingame::DEATH_LINE;
+use crate::SCREEN_HEIGHT;
+use crate::SCREEN_WIDTH;
+
+//player two's playfield sits far enough away that the two never see each other
+pub const PLAYFIELD_OFFSET: f32 = 4000.0;
+//each half of the screen shows a whole playfield
+pub const SPLIT_CAMERA_SCALE: f32 = 2.0;
+const GAMEPAD_CROSSHAIR_SPEED: f32 = 1400.0;
+const GAMEPAD_DEADZONE: f32 = 0.15;
+
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Player {
+    #[default]
+    One,
+    Two,
+}
+
+impl Player {
+    pub const ALL: [Player; 2] = [Player::One, Player::Two];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Player::One => "PLAYER 1",
+            Player::Two => "PLAYER 2",
+        }
+    }
+
+    //translation key of the name shown on screen
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            Player::One => "player_one",
+            Player::Two => "player_two",
+        }
+    }
+
+    pub fn other(&self) -> Player {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
+
+    //world position of the middle of this player's playfield
+    pub fn origin(&self) -> Vec2 {
+        match self {
+            Player::One => Vec2::ZERO,
+            Player::Two => Vec2::new(PLAYFIELD_OFFSET, 0.0),
+        }
+    }
+}
+
+//the camera the menus and the hud are drawn with
+#[derive(Component)]
+pub struct MainCamera;
+
+#[derive(Component)]
+pub struct PlayerCamera(pub Player);
+
+//results of the current hot-seat or split-screen match
+#[derive(Resource, Default)]
+pub struct Versus {
+    //whose turn it is in hot-seat
+    pub turn: Player,
+    pub scores: [Option<i32>; 2],
+    //split-screen is lost by the first player to drop their ball
+    pub dropped: Option<Player>,
+    //both hot-seat runs get the same balls
+    pub seed: u64,
+}
+
+impl Versus {
+    pub fn new_match() -> Self {
+        Self {
+            seed: rand::thread_rng().gen(),
+            ..default()
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.scores.iter().all(Option::is_some)
+    }
+
+    //None is a draw
+    pub fn winner(&self) -> Option<Player> {
+        if let Some(dropped) = self.dropped {
+            return Some(dropped.other());
+        }
+
+        match self.scores {
+            [Some(one), Some(two)] if one > two => Some(Player::One),
+            [Some(one), Some(two)] if two > one => Some(Player::Two),
+            _ => None,
+        }
+    }
+}
+
+pub fn versus_setup(mode: Res<GameMode>, mut versus: ResMut<Versus>) {
+    match *mode {
+        GameMode::HotSeat => {
+            if versus.finished() {
+                *versus = Versus::new_match();
+            }
+            versus.turn = if versus.scores[0].is_some() {
+                Player::Two
+            } else {
+                Player::One
+            };
+            info!("Hot-seat: {} up", versus.turn.name());
+        }
+        GameMode::SplitScreen => *versus = Versus::default(),
+        _ => {}
+    }
+}
+
+//one viewport per player, the main camera only draws the hud on top of them.
+//bloom and tonemapping cover the whole screen, so they stay on the main camera only
+pub fn split_screen_setup(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    windows: Query<&Window>,
+    mut main_camera: Query<(&mut Camera2d, &mut Transform), With<MainCamera>>,
+) {
+    if *mode != GameMode::SplitScreen {
+        return;
+    }
+
+    let window = windows.single();
+
+    for player in Player::ALL {
+        let origin = player.origin();
+
+        commands
+            .spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        hdr: true,
+                        order: player.index() as isize - 2,
+                        viewport: Some(split_viewport(window, player)),
+                        ..default()
+                    },
+                    camera_2d: Camera2d {
+                        //the second camera must not clear the first one's half
+                        clear_color: match player {
+                            Player::One => ClearColorConfig::Default,
+                            Player::Two => ClearColorConfig::None,
+                        },
+                    },
+                    projection: OrthographicProjection {
+                        scale: SPLIT_CAMERA_SCALE,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(origin.x, origin.y, 999.0),
+                    tonemapping: Tonemapping::None,
+                    ..default()
+                },
+                UiCameraConfig { show_ui: false },
+            ))
+            .insert(PlayerCamera(player))
+            .insert(InGameEntity);
+    }
+
+    //look at empty space so only the hud is left
+    let (mut camera_2d, mut transform) = main_camera.single_mut();
+    camera_2d.clear_color = ClearColorConfig::None;
+    transform.translation.y = SCREEN_HEIGHT * 10.0;
+}
+
+pub fn split_screen_restore(
+    mut main_camera: Query<(&mut Camera2d, &mut Transform), With<MainCamera>>,
+) {
+    let (mut camera_2d, mut transform) = main_camera.single_mut();
+    camera_2d.clear_color = ClearColorConfig::Default;
+    transform.translation.y = 0.0;
+}
+
+fn split_viewport(window: &Window, player: Player) -> Viewport {
+    let half_width = window.physical_width() / 2;
+
+    Viewport {
+        physical_position: UVec2::new(half_width * player.index() as u32, 0),
+        physical_size: UVec2::new(half_width, window.physical_height()),
+        ..default()
+    }
+}
+
+//player two aims with the left stick of the first gamepad
+pub fn gamepad_crosshair(
+    time: Res<Time>,
+    rules: Res<RunRules>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut crosshairs: Query<(&mut Transform, &Player), With<CursorCrosshair>>,
+) {
+    let Some(gamepad) = gamepads.iter().next() else {
+        return;
+    };
+
+    let stick = Vec2::new(
+        axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0),
+        axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0),
+    );
+
+    if stick.length() < GAMEPAD_DEADZONE {
+        return;
+    }
+
+    for (mut transform, player) in &mut crosshairs {
+        if *player != Player::Two {
+            continue;
+        }
+
+        let origin = rules.origin(*player);
+        let position = transform.translation.truncate()
+            + stick * GAMEPAD_CROSSHAIR_SPEED * time.delta_seconds();
+        let bounds = Vec2::new(SCREEN_WIDTH / 2.0, SCREEN_HEIGHT / 2.0);
+        let position = position.clamp(origin - bounds, origin + bounds);
+
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+pub fn gamepad_fire_pressed(gamepads: &Gamepads, buttons: &Input<GamepadButton>) -> bool {
+    gamepads.iter().next().is_some_and(|gamepad| {
+        buttons.any_just_pressed([
+            GamepadButton::new(gamepad, GamepadButtonType::South),
+            GamepadButton::new(gamepad, GamepadButtonType::RightTrigger2),
+        ])
+    })
+}
+
+pub fn versus_recorder(mode: Res<GameMode>, scores: Res<Scores>, mut versus: ResMut<Versus>) {
+    match *mode {
+        GameMode::HotSeat => {
+            let turn = versus.turn.index();
+            versus.scores[turn] = Some(scores.current_score);
+        }
+        GameMode::SplitScreen => {
+            versus.scores = [
+                Some(scores.player_score(Player::One)),
+                Some(scores.player_score(Player::Two)),
+            ];
+        }
+        _ => {}
+    }
+}
+
+//balls of the player that dropped first decide the split-screen match
+pub fn split_screen_drop_checker(
+    balls: Query<(&Transform, &Player), With<Ball>>,
+    mut versus: ResMut<Versus>,
+) {
+    if versus.dropped.is_some() {
+        return;
+    }
+
+    for player in Player::ALL {
+        let mut player_balls = balls.iter().filter(|(_, owner)| **owner == player);
+
+        if player_balls.all(|(transform, _)| transform.translation.y < DEATH_LINE) {
+            info!("{} dropped the ball", player.name());
+            versus.dropped = Some(player);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versus(scores: [Option<i32>; 2], dropped: Option<Player>) -> Versus {
+        Versus {
+            scores,
+            dropped,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn higher_score_wins_once_both_played() {
+        assert_eq!(
+            versus([Some(12), Some(7)], None).winner(),
+            Some(Player::One)
+        );
+        assert_eq!(versus([Some(3), Some(9)], None).winner(), Some(Player::Two));
+    }
+
+    #[test]
+    fn equal_scores_are_a_draw() {
+        assert_eq!(versus([Some(5), Some(5)], None).winner(), None);
+    }
+
+    #[test]
+    fn dropping_first_loses_whatever_the_score() {
+        let split_screen = versus([Some(40), Some(2)], Some(Player::One));
+
+        assert_eq!(split_screen.winner(), Some(Player::Two));
+    }
+
+    #[test]
+    fn match_finishes_with_both_scores() {
+        assert!(!versus([None, None], None).finished());
+        assert!(!versus([Some(4), None], None).finished());
+        assert_eq!(versus([Some(4), None], None).winner(), None);
+        assert!(versus([Some(4), Some(0)], None).finished());
+    }
+
+    #[test]
+    fn players_face_each_other() {
+        for player in Player::ALL {
+            assert_eq!(player.other().other(), player);
+            assert_ne!(player.other(), player);
+            assert_eq!(Player::ALL[player.index()], player);
+        }
+    }
+}
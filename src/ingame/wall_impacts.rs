@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::ingame::BulletTime;
+use crate::ingame::InGameEntity;
+use crate::ingame::JumpBallEvent;
+use crate::ingame::Player;
+use crate::ingame::RunRules;
+use crate::ingame::Scores;
+use crate::ingame::Wall;
+use crate::ingame::WallHitEvent;
+use crate::localization::Localization;
+
+const WALL_HIT_SOUND: &str = "sounds/wall_thud.ogg";
+//impacts slower than this are silent, full volume from the loud speed on
+const WALL_QUIET_SPEED: f32 = 100.0;
+const WALL_LOUD_SPEED: f32 = 1500.0;
+//resting and brightest alpha of the translucent wall sprite
+const WALL_ALPHA: f32 = 0.1;
+const WALL_FLASH_ALPHA: f32 = 0.5;
+const WALL_FLASH_SECONDS: f32 = 0.3;
+//a wall hit this soon after the ball was shot is a bank shot
+const BANK_SHOT_WINDOW: f32 = 0.6;
+const BANK_SHOT_BONUS: i32 = 2;
+
+#[derive(Component)]
+pub struct WallFlash {
+    timer: Timer,
+}
+
+//time left for the ball's last shot to turn into a bank shot
+#[derive(Component)]
+pub struct BankShotWindow {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct BankShotText {
+    lifetime: Timer,
+}
+
+pub fn wall_hit_sound(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    mut wall_hit_reader: EventReader<WallHitEvent>,
+) {
+    for event in wall_hit_reader.iter() {
+        if event.speed < WALL_QUIET_SPEED {
+            continue;
+        }
+
+        let volume = ((event.speed - WALL_QUIET_SPEED) / (WALL_LOUD_SPEED - WALL_QUIET_SPEED))
+            .clamp(0.1, 1.0);
+        audio
+            .play(asset_server.load(WALL_HIT_SOUND))
+            .with_volume(volume as f64);
+    }
+}
+
+pub fn wall_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut wall_hit_reader: EventReader<WallHitEvent>,
+    mut walls: Query<(Entity, &mut Sprite, Option<&mut WallFlash>), With<Wall>>,
+) {
+    for event in wall_hit_reader.iter() {
+        if walls.contains(event.wall) {
+            commands.entity(event.wall).insert(WallFlash {
+                timer: Timer::from_seconds(WALL_FLASH_SECONDS, TimerMode::Once),
+            });
+        }
+    }
+
+    for (entity, mut sprite, flash) in &mut walls {
+        let Some(mut flash) = flash else {
+            continue;
+        };
+
+        flash.timer.tick(time.delta());
+        let brightness = flash.timer.percent_left();
+        sprite
+            .color
+            .set_a(WALL_ALPHA + (WALL_FLASH_ALPHA - WALL_ALPHA) * brightness);
+
+        if flash.timer.finished() {
+            commands.entity(entity).remove::<WallFlash>();
+        }
+    }
+}
+
+pub fn bank_shot_window(
+    mut commands: Commands,
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    mut jump_event_reader: EventReader<JumpBallEvent>,
+    mut windows: Query<(Entity, &mut BankShotWindow)>,
+) {
+    for (entity, mut window) in &mut windows {
+        window.timer.tick(bullet_time.scaled(time.delta()));
+
+        if window.timer.finished() {
+            commands.entity(entity).remove::<BankShotWindow>();
+        }
+    }
+
+    //shooting the ball again restarts its window
+    for event in jump_event_reader.iter() {
+        if let Some(mut ball) = commands.get_entity(event.ball) {
+            ball.insert(BankShotWindow {
+                timer: Timer::from_seconds(BANK_SHOT_WINDOW, TimerMode::Once),
+            });
+        }
+    }
+}
+
+pub fn bank_shot_bonus(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    rules: Res<RunRules>,
+    mut scores: ResMut<Scores>,
+    mut wall_hit_reader: EventReader<WallHitEvent>,
+    balls: Query<&Player, With<BankShotWindow>>,
+) {
+    for event in wall_hit_reader.iter() {
+        if !rules.bank_shots {
+            continue;
+        }
+        let Ok(player) = balls.get(event.ball) else {
+            continue;
+        };
+
+        //one bonus per shot
+        commands.entity(event.ball).remove::<BankShotWindow>();
+        *scores.player_score_mut(*player) += BANK_SHOT_BONUS;
+        info!("Bank shot for {}", player.name());
+
+        let position = event.position + event.normal * 120.0;
+        commands
+            .spawn(Text2dBundle {
+                text: Text::from_section(
+                    localization.format("bank_shot", &[&BANK_SHOT_BONUS]),
+                    TextStyle {
+                        font: localization.font(&asset_server),
+                        font_size: 36.0,
+                        color: Color::YELLOW,
+                    },
+                ),
+                transform: Transform::from_xyz(position.x, position.y, 5.0),
+                ..default()
+            })
+            .insert(BankShotText {
+                lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+            })
+            .insert(InGameEntity);
+    }
+}
+
+pub fn bank_shot_text_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bank_texts: Query<(Entity, &mut BankShotText, &mut Text, &mut Transform)>,
+) {
+    for (entity, mut bank_text, mut text, mut transform) in &mut bank_texts {
+        bank_text.lifetime.tick(time.delta());
+
+        transform.translation.y += 60.0 * time.delta_seconds();
+        text.sections[0]
+            .style
+            .color
+            .set_a(bank_text.lifetime.percent_left());
+
+        if bank_text.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
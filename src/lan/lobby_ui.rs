@@ -0,0 +1,276 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::ingame::ball_types::BallSelection;
+use crate::ingame::rules::GameMode;
+use crate::lan::protocol::{MatchSettings, NetMessage, RELAY_PORT};
+use crate::lan::relay::Relay;
+use crate::lan::{LanClient, LanMatch};
+use crate::localization::Localization;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonPressedEvent;
+use crate::widgets::ButtonSpec;
+use crate::widgets::UiTheme;
+use crate::AppState;
+use crate::GameDifficultyState;
+
+#[derive(Component, Clone, Copy)]
+pub enum LobbyAction {
+    Host,
+    Join,
+}
+
+#[derive(Resource, Default, Clone, PartialEq, Eq)]
+pub enum LobbyStatus {
+    #[default]
+    Idle,
+    Searching,
+    Waiting,
+    Failed(String),
+}
+
+impl LobbyStatus {
+    fn text(&self, localization: &Localization) -> String {
+        match self {
+            LobbyStatus::Idle => localization.text("lobby_idle"),
+            LobbyStatus::Searching => localization.text("lobby_searching"),
+            LobbyStatus::Waiting => localization.format("lobby_waiting", &[&RELAY_PORT]),
+            LobbyStatus::Failed(error) => localization.format("lobby_failed", &[error]),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct LobbyStatusText;
+
+#[derive(Component)]
+pub struct LobbyEntity;
+
+//how often join is repeated until the relay answers
+const JOIN_RETRY_SECONDS: f32 = 0.5;
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    theme: Res<UiTheme>,
+    client: Option<Res<LanClient>>,
+    mut status: ResMut<LobbyStatus>,
+) {
+    info!("Lobby activated");
+
+    let font = localization.font(&asset_server);
+
+    //a client left over from the last match is waiting for the rematch
+    *status = if client.is_some() {
+        LobbyStatus::Waiting
+    } else {
+        LobbyStatus::Idle
+    };
+
+    //create full screen node bundle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(30.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(LobbyEntity)
+        //create yellow title background
+        .with_children(|parent| {
+            parent
+                .spawn(title_panel(&theme, 800.0, 100.0))
+                //"lan versus" text
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        localization.text("lan_versus"),
+                        &font,
+                        100.0,
+                        theme.title_text,
+                    ));
+                });
+        })
+        //status: create black background
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(800.0),
+                        height: Val::Px(60.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    background_color: Color::BLACK.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        text(status.text(&localization), &font, 36.0, Color::WHITE),
+                        LobbyStatusText,
+                    ));
+                });
+        })
+        //create "host", "join" and "main menu" buttons
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(75.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                //spawn "host", "join" and "main menu" buttons
+                .with_children(|parent| {
+                    for (action, key) in [(LobbyAction::Host, "host"), (LobbyAction::Join, "join")]
+                    {
+                        spawn_button(
+                            parent,
+                            &theme,
+                            &font,
+                            ButtonSpec::new(localization.text(key)),
+                        )
+                        .insert(action);
+                    }
+                    spawn_button(
+                        parent,
+                        &theme,
+                        &font,
+                        ButtonSpec::new(localization.text("main_menu")).open(AppState::MainMenu),
+                    );
+                });
+        });
+}
+
+pub fn lobby_actions(
+    mut commands: Commands,
+    difficulty: Res<State<GameDifficultyState>>,
+    ball_selection: Res<BallSelection>,
+    client: Option<Res<LanClient>>,
+    mut status: ResMut<LobbyStatus>,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    buttons: Query<&LobbyAction>,
+) {
+    for event in pressed_reader.iter() {
+        let Ok(action) = buttons.get(event.entity) else {
+            continue;
+        };
+
+        if client.is_some() {
+            continue;
+        }
+
+        match action {
+            LobbyAction::Host => {
+                //the host picks the difficulty and ball for both players
+                let settings = MatchSettings {
+                    seed: rand::thread_rng().gen(),
+                    difficulty: *difficulty.get(),
+                    ball: *ball_selection,
+                };
+
+                let hosted = Relay::start(settings)
+                    .and_then(|relay| LanClient::host().map(|client| (relay, client)));
+
+                match hosted {
+                    Ok((relay, client)) => {
+                        client.send_join();
+                        commands.insert_resource(relay);
+                        commands.insert_resource(client);
+                        *status = LobbyStatus::Waiting;
+                    }
+                    Err(error) => {
+                        warn!("Couldn't host LAN match: {}", error);
+                        *status = LobbyStatus::Failed(error.to_string().to_uppercase());
+                    }
+                }
+            }
+            LobbyAction::Join => match LanClient::join() {
+                Ok(client) => {
+                    client.send_join();
+                    commands.insert_resource(client);
+                    *status = LobbyStatus::Searching;
+                }
+                Err(error) => {
+                    warn!("Couldn't join LAN match: {}", error);
+                    *status = LobbyStatus::Failed(error.to_string().to_uppercase());
+                }
+            },
+        }
+    }
+}
+
+//keep asking the relay to join until it starts the match
+pub fn lobby_poll(
+    mut commands: Commands,
+    time: Res<Time>,
+    client: Option<ResMut<LanClient>>,
+    mut status: ResMut<LobbyStatus>,
+    mut retry_timer: Local<Timer>,
+) {
+    let Some(mut client) = client else {
+        return;
+    };
+
+    retry_timer.tick(time.delta());
+    if retry_timer.finished() {
+        client.send_join();
+        *retry_timer = Timer::from_seconds(JOIN_RETRY_SECONDS, TimerMode::Once);
+    }
+
+    for message in client.receive() {
+        match message {
+            NetMessage::Waiting => *status = LobbyStatus::Waiting,
+            //starts for an earlier match can still be on their way
+            NetMessage::Start(round, settings) if round == client.round => {
+                info!("LAN match started: {:?}", settings);
+
+                client.round += 1;
+                commands.insert_resource(LanMatch(settings));
+                commands.insert_resource(GameMode::Lan);
+                commands.insert_resource(NextState(Some(AppState::InGame)));
+                commands.insert_resource(NextState(Some(settings.difficulty)));
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn lobby_status_update(
+    localization: Res<Localization>,
+    status: Res<LobbyStatus>,
+    mut texts: Query<&mut Text, With<LobbyStatusText>>,
+) {
+    if !status.is_changed() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value = status.text(&localization);
+    }
+}
+
+pub fn entity_despawner(mut entities: Query<Entity, With<LobbyEntity>>, mut commands: Commands) {
+    info!("Lobby Despawner Activated");
+
+    //despawn everyting in Lobby
+    for entities_despawner in &mut entities {
+        commands.entity(entities_despawner).despawn_recursive();
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+};
+
+use bevy::prelude::*;
+
+mod lobby_ui;
+pub mod protocol;
+mod relay;
+
+use crate::ingame::gameplay::DEATH_LINE;
+use crate::ingame::rules::GameMode;
+use crate::ingame::spawn::{Ball, InGameEntity};
+use crate::ingame::Scores;
+use crate::localization::Localization;
+use crate::AppState;
+use crate::GameDifficultyState;
+use lobby_ui::*;
+use protocol::*;
+
+//snapshots sent per second while playing
+const SNAPSHOT_RATE: f32 = 20.0;
+const GHOST_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.3);
+
+#[derive(Resource)]
+pub struct LanClient {
+    socket: UdpSocket,
+    //unknown until a relay answers our broadcast
+    relay: Option<SocketAddr>,
+    frame: u32,
+    //matches started over this connection, tells a rematch from a stale start
+    round: u32,
+}
+
+impl LanClient {
+    pub fn host() -> io::Result<LanClient> {
+        let mut client = LanClient::join()?;
+        client.relay = Some(SocketAddr::from((Ipv4Addr::LOCALHOST, RELAY_PORT)));
+        Ok(client)
+    }
+
+    pub fn join() -> io::Result<LanClient> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_nonblocking(true)?;
+        socket.set_broadcast(true)?;
+
+        Ok(LanClient {
+            socket,
+            relay: None,
+            frame: 0,
+            round: 0,
+        })
+    }
+
+    pub fn send_join(&self) {
+        //look for a relay on the network and on this machine
+        let targets = match self.relay {
+            Some(relay) => vec![relay],
+            None => vec![
+                SocketAddr::from((Ipv4Addr::BROADCAST, RELAY_PORT)),
+                SocketAddr::from((Ipv4Addr::LOCALHOST, RELAY_PORT)),
+            ],
+        };
+
+        for target in targets {
+            self.send_to(NetMessage::Join(self.round), target);
+        }
+    }
+
+    pub fn send(&self, message: NetMessage) {
+        if let Some(relay) = self.relay {
+            self.send_to(message, relay);
+        }
+    }
+
+    fn send_to(&self, message: NetMessage, to: SocketAddr) {
+        if let Err(error) = self.socket.send_to(&message.encode(), to) {
+            warn!("LAN message to {} failed: {}", to, error);
+        }
+    }
+
+    pub fn receive(&mut self) -> Vec<NetMessage> {
+        let mut messages = Vec::new();
+        let mut buffer = [0; MAX_MESSAGE_SIZE];
+
+        //read until the socket would block
+        while let Ok((length, from)) = self.socket.recv_from(&mut buffer) {
+            if let Some(message) = NetMessage::decode(&buffer[..length]) {
+                self.relay.get_or_insert(from);
+                messages.push(message);
+            }
+        }
+
+        messages
+    }
+}
+
+//settings of the match the relay started
+#[derive(Resource, Default)]
+pub struct LanMatch(pub MatchSettings);
+
+#[derive(Resource, Default)]
+pub struct LanOpponent {
+    pub snapshot: Option<Snapshot>,
+    pub left: bool,
+}
+
+impl LanOpponent {
+    pub fn playing(&self) -> bool {
+        !self.left && self.snapshot.is_none_or(|snapshot| snapshot.alive)
+    }
+}
+
+#[derive(Component)]
+pub struct OpponentGhost;
+
+#[derive(Component)]
+pub struct OpponentScoreText;
+
+pub struct LanPlugin;
+
+impl Plugin for LanPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LanMatch>()
+            .init_resource::<LanOpponent>()
+            .init_resource::<LobbyStatus>()
+            //coming back from a match keeps the connection for a rematch
+            .add_systems(OnEnter(AppState::Lobby), setup)
+            .add_systems(
+                Update,
+                (lobby_actions, lobby_poll, lobby_status_update).run_if(in_state(AppState::Lobby)),
+            )
+            .add_systems(OnExit(AppState::Lobby), entity_despawner)
+            .add_systems(OnEnter(AppState::MainMenu), lan_disconnect)
+            .add_systems(
+                OnEnter(AppState::InGame),
+                lan_ghost_setup.run_if(resource_equals(GameMode::Lan)),
+            )
+            .add_systems(
+                Update,
+                (
+                    lan_receive,
+                    lan_snapshot_send,
+                    lan_ghost_update.after(lan_receive),
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_equals(GameMode::Lan)),
+            )
+            //whoever drops first still has to hear how the opponent's run ends
+            .add_systems(
+                Update,
+                lan_receive
+                    .run_if(in_state(AppState::GameOver))
+                    .run_if(resource_equals(GameMode::Lan)),
+            )
+            .add_systems(
+                OnExit(AppState::InGame),
+                lan_final_snapshot.run_if(resource_equals(GameMode::Lan)),
+            );
+    }
+}
+
+pub fn lan_disconnect(mut commands: Commands, client: Option<Res<LanClient>>) {
+    if let Some(client) = client {
+        client.send(NetMessage::Leave);
+        commands.remove_resource::<LanClient>();
+        //dropping the relay stops its thread
+        commands.remove_resource::<relay::Relay>();
+        info!("Left LAN match");
+    }
+}
+
+pub fn lan_ghost_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    difficulty: Res<State<GameDifficultyState>>,
+    mut opponent: ResMut<LanOpponent>,
+) {
+    *opponent = LanOpponent::default();
+
+    let ball = Ball::for_difficulty(*difficulty.get());
+
+    //opponent's ball drawn behind ours
+    commands
+        .spawn(SpriteBundle {
+            texture: asset_server.load(ball.sprite()),
+            sprite: Sprite {
+                color: GHOST_COLOR,
+                custom_size: Some(Vec2::splat(ball.radius() * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -7.0),
+            ..default()
+        })
+        .insert(OpponentGhost)
+        .insert(InGameEntity);
+
+    commands
+        .spawn(TextBundle::from_section(
+            localization.format("opponent", &[&0]),
+            TextStyle {
+                font: localization.font(&asset_server),
+                font_size: 40.0,
+                color: Color::rgb(0.9, 0.55, 0.2),
+            },
+        ))
+        .insert(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            ..default()
+        })
+        .insert(OpponentScoreText)
+        .insert(InGameEntity);
+}
+
+pub fn lan_receive(mut client: ResMut<LanClient>, mut opponent: ResMut<LanOpponent>) {
+    for message in client.receive() {
+        match message {
+            NetMessage::Opponent(snapshot) => {
+                let newer = opponent
+                    .snapshot
+                    .is_none_or(|last| snapshot.frame > last.frame);
+
+                if newer {
+                    opponent.snapshot = Some(snapshot);
+                }
+            }
+            NetMessage::OpponentLeft => {
+                info!("Opponent left the LAN match");
+                opponent.left = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn lan_snapshot_send(
+    time: Res<Time>,
+    scores: Res<Scores>,
+    mut client: ResMut<LanClient>,
+    mut timer: Local<Timer>,
+    balls: Query<&Transform, With<Ball>>,
+) {
+    timer.tick(time.delta());
+
+    if !timer.finished() {
+        return;
+    }
+
+    *timer = Timer::from_seconds(1.0 / SNAPSHOT_RATE, TimerMode::Once);
+
+    let snapshot = local_snapshot(&mut client, &scores, &balls);
+    client.send(NetMessage::Snapshot(snapshot));
+}
+
+//let the opponent know we're out, even though the last regular snapshot may be late
+pub fn lan_final_snapshot(
+    scores: Res<Scores>,
+    mut client: ResMut<LanClient>,
+    balls: Query<&Transform, With<Ball>>,
+) {
+    let snapshot = Snapshot {
+        alive: false,
+        ..local_snapshot(&mut client, &scores, &balls)
+    };
+    client.send(NetMessage::Snapshot(snapshot));
+}
+
+fn local_snapshot(
+    client: &mut LanClient,
+    scores: &Scores,
+    balls: &Query<&Transform, With<Ball>>,
+) -> Snapshot {
+    client.frame += 1;
+
+    //the highest ball is the one worth showing
+    let ball = balls
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .max_by(|a, b| a.y.total_cmp(&b.y))
+        .unwrap_or_default();
+
+    Snapshot {
+        frame: client.frame,
+        score: scores.current_score,
+        ball: ball.to_array(),
+        alive: ball.y >= DEATH_LINE,
+    }
+}
+
+pub fn lan_ghost_update(
+    localization: Res<Localization>,
+    opponent: Res<LanOpponent>,
+    mut ghosts: Query<(&mut Transform, &mut Visibility), With<OpponentGhost>>,
+    mut texts: Query<&mut Text, With<OpponentScoreText>>,
+) {
+    //an opponent can leave before a single snapshot arrived, a finished one keeps their score
+    if opponent.left && opponent.snapshot.is_none_or(|snapshot| snapshot.alive) {
+        for (_, mut visibility) in &mut ghosts {
+            *visibility = Visibility::Hidden;
+        }
+        for mut text in &mut texts {
+            text.sections[0].value = localization.text("opponent_left");
+        }
+        return;
+    }
+
+    let Some(snapshot) = opponent.snapshot else {
+        return;
+    };
+
+    for (mut transform, mut visibility) in &mut ghosts {
+        transform.translation.x = snapshot.ball[0];
+        transform.translation.y = snapshot.ball[1];
+        *visibility = if opponent.playing() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value = if snapshot.alive {
+            localization.format("opponent", &[&snapshot.score])
+        } else {
+            localization.format("opponent_out", &[&snapshot.score])
+        };
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ingame::ball_types::BallSelection;
+use crate::GameDifficultyState;
+
+pub const RELAY_PORT: u16 = 47777;
+//every message fits in one datagram
+pub const MAX_MESSAGE_SIZE: usize = 512;
+
+//what both players need to get the same run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchSettings {
+    pub seed: u64,
+    pub difficulty: GameDifficultyState,
+    pub ball: BallSelection,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    //snapshots can arrive out of order, older ones are dropped
+    pub frame: u32,
+    pub score: i32,
+    pub ball: [f32; 2],
+    pub alive: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NetMessage {
+    //client to relay, with the number of matches the client started so far
+    Join(u32),
+    Leave,
+    Snapshot(Snapshot),
+    //relay to client, with the match number the join asked for
+    Waiting,
+    Start(u32, MatchSettings),
+    Opponent(Snapshot),
+    OpponentLeft,
+}
+
+impl NetMessage {
+    pub fn encode(&self) -> Vec<u8> {
+        ron::to_string(self)
+            .expect("net messages always serialize")
+            .into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<NetMessage> {
+        let text = std::str::from_utf8(bytes).ok()?;
+        ron::from_str(text).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingame::ball_types::BallKind;
+
+    fn messages() -> Vec<NetMessage> {
+        let settings = MatchSettings {
+            seed: u64::MAX,
+            difficulty: GameDifficultyState::Hard,
+            ball: BallSelection::Fixed(BallKind::Splitting),
+        };
+        //the largest values a snapshot can carry
+        let snapshot = Snapshot {
+            frame: u32::MAX,
+            score: i32::MIN,
+            ball: [f32::MIN, -f32::MIN_POSITIVE],
+            alive: false,
+        };
+
+        vec![
+            NetMessage::Join(0),
+            NetMessage::Join(u32::MAX),
+            NetMessage::Leave,
+            NetMessage::Snapshot(snapshot),
+            NetMessage::Waiting,
+            NetMessage::Start(u32::MAX, settings),
+            NetMessage::Start(0, MatchSettings::default()),
+            NetMessage::Opponent(snapshot),
+            NetMessage::OpponentLeft,
+        ]
+    }
+
+    #[test]
+    fn every_message_survives_a_round_trip() {
+        for message in messages() {
+            assert_eq!(NetMessage::decode(&message.encode()), Some(message));
+        }
+    }
+
+    #[test]
+    fn every_message_fits_in_one_datagram() {
+        for message in messages() {
+            let length = message.encode().len();
+
+            assert!(
+                length <= MAX_MESSAGE_SIZE,
+                "{:?}: {} bytes",
+                message,
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn garbage_is_dropped() {
+        let encoded = NetMessage::Start(3, MatchSettings::default()).encode();
+
+        assert_eq!(NetMessage::decode(&encoded[..encoded.len() / 2]), None);
+        assert_eq!(NetMessage::decode(b"Shutdown"), None);
+        assert_eq!(NetMessage::decode(&[0xff, 0xfe, 0x00]), None);
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    io,
+    net::{SocketAddr, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use crate::lan::protocol::{MatchSettings, NetMessage, MAX_MESSAGE_SIZE, RELAY_PORT};
+
+//the host runs the relay next to the game, both players talk to it the same way
+#[derive(Resource)]
+pub struct Relay {
+    stop: Arc<AtomicBool>,
+}
+
+impl Relay {
+    pub fn start(settings: MatchSettings) -> io::Result<Relay> {
+        let socket = UdpSocket::bind(("0.0.0.0", RELAY_PORT))?;
+        socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        thread::Builder::new()
+            .name("lan relay".into())
+            .spawn(move || relay_loop(socket, settings, thread_stop))?;
+
+        info!("LAN relay listening on port {}", RELAY_PORT);
+        Ok(Relay { stop })
+    }
+}
+
+impl Drop for Relay {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+//a player's spot in the room
+struct Seat {
+    address: SocketAddr,
+    //matches this player was started in
+    started: u32,
+    //joined for the next match
+    ready: bool,
+}
+
+fn relay_loop(socket: UdpSocket, settings: MatchSettings, stop: Arc<AtomicBool>) {
+    let mut seats: Vec<Seat> = Vec::new();
+    let mut current = settings;
+    let mut matches: u64 = 0;
+    let mut buffer = [0; MAX_MESSAGE_SIZE];
+
+    let send = |message: NetMessage, to: SocketAddr| {
+        if let Err(error) = socket.send_to(&message.encode(), to) {
+            warn!("LAN relay couldn't reach {}: {}", to, error);
+        }
+    };
+
+    while !stop.load(Ordering::Relaxed) {
+        let (length, from) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            //read timeout, check the stop flag again
+            Err(_) => continue,
+        };
+
+        let Some(message) = NetMessage::decode(&buffer[..length]) else {
+            continue;
+        };
+
+        match message {
+            NetMessage::Join(round) => {
+                if !seats.iter().any(|seat| seat.address == from) {
+                    //strangers can't take a seat in a full room
+                    if seats.len() >= 2 {
+                        continue;
+                    }
+
+                    info!("LAN relay: {} joined", from);
+                    seats.push(Seat {
+                        address: from,
+                        started: 0,
+                        ready: false,
+                    });
+                }
+
+                let Some(seat) = seats.iter_mut().find(|seat| seat.address == from) else {
+                    continue;
+                };
+
+                //joins are repeated until the start arrives, answer late ones with it again
+                if round + 1 == seat.started {
+                    send(NetMessage::Start(round, current), from);
+                    continue;
+                }
+
+                if round != seat.started {
+                    continue;
+                }
+
+                seat.ready = true;
+
+                //every match, rematches too, needs a fresh join from both players
+                if seats.len() == 2 && seats.iter().all(|seat| seat.ready) {
+                    current = MatchSettings {
+                        seed: settings.seed.wrapping_add(matches),
+                        ..settings
+                    };
+                    matches += 1;
+
+                    for seat in &mut seats {
+                        send(NetMessage::Start(seat.started, current), seat.address);
+                        seat.started += 1;
+                        seat.ready = false;
+                    }
+                } else {
+                    send(NetMessage::Waiting, from);
+                }
+            }
+            NetMessage::Leave => {
+                //frees the seat for someone else
+                seats.retain(|seat| seat.address != from);
+                for seat in &seats {
+                    send(NetMessage::OpponentLeft, seat.address);
+                }
+            }
+            NetMessage::Snapshot(snapshot) => {
+                for seat in seats.iter().filter(|seat| seat.address != from) {
+                    send(NetMessage::Opponent(snapshot), seat.address);
+                }
+            }
+            //only the relay sends these
+            NetMessage::Waiting
+            | NetMessage::Start(..)
+            | NetMessage::Opponent(_)
+            | NetMessage::OpponentLeft => {}
+        }
+    }
+
+    info!("LAN relay stopped");
+}
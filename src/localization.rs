@@ -0,0 +1,273 @@
+use std::{collections::HashMap, fmt::Display};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+    Georgian,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::German,
+        Language::Spanish,
+        Language::Georgian,
+    ];
+
+    pub fn next(&self) -> Language {
+        let index = Language::ALL.iter().position(|language| language == self);
+        Language::ALL[index.map_or(0, |index| (index + 1) % Language::ALL.len())]
+    }
+
+    //translation files are compiled in, like the rest of the assets
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en.ron"),
+            Language::German => include_str!("../assets/locales/de.ron"),
+            Language::Spanish => include_str!("../assets/locales/es.ron"),
+            Language::Georgian => include_str!("../assets/locales/ka.ron"),
+        }
+    }
+
+    //NotoSans has no georgian, DejaVuSans does
+    fn font(&self) -> &'static str {
+        match self {
+            Language::English | Language::German | Language::Spanish => "fonts/NotoSans-Medium.ttf",
+            Language::Georgian => "fonts/DejaVuSans.ttf",
+        }
+    }
+}
+
+//one translation file, `{}` in a string is filled in by `Localization::format`
+#[derive(Deserialize)]
+struct LocaleFile {
+    //the language's own name, shown in the language selector
+    name: String,
+    strings: HashMap<String, String>,
+}
+
+impl LocaleFile {
+    fn parse(language: Language) -> LocaleFile {
+        ron::from_str(language.source()).unwrap_or_else(|error| {
+            error!("Translation file for {:?} is broken: {}", language, error);
+            LocaleFile {
+                name: format!("{:?}", language).to_uppercase(),
+                strings: HashMap::new(),
+            }
+        })
+    }
+}
+
+//ui text of the selected language, english fills in whatever is missing
+#[derive(Resource)]
+pub struct Localization {
+    pub language: Language,
+    locale: LocaleFile,
+    fallback: LocaleFile,
+}
+
+impl Localization {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            locale: LocaleFile::parse(language),
+            fallback: LocaleFile::parse(Language::English),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.locale.name
+    }
+
+    pub fn text(&self, key: &str) -> String {
+        self.locale
+            .strings
+            .get(key)
+            .or_else(|| self.fallback.strings.get(key))
+            .cloned()
+            .unwrap_or_else(|| {
+                warn!("Missing translation for {:?}", key);
+                key.to_string()
+            })
+    }
+
+    //replaces each `{}` with the next argument, in order
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        let text = self.text(key);
+        let mut parts = text.split("{}");
+        let mut result = parts.next().unwrap_or_default().to_string();
+
+        for (index, part) in parts.enumerate() {
+            match args.get(index) {
+                Some(arg) => result.push_str(&arg.to_string()),
+                None => result.push_str("{}"),
+            }
+            result.push_str(part);
+        }
+
+        result
+    }
+
+    pub fn font(&self, asset_server: &AssetServer) -> Handle<Font> {
+        asset_server.load(self.language.font())
+    }
+}
+
+//text that follows the language when it's changed on screen
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+pub fn localized_text_update(
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    mut texts: Query<(&LocalizedText, &mut Text)>,
+) {
+    for (localized, mut text) in &mut texts {
+        text.sections[0].value = localization.text(localized.0);
+        text.sections[0].style.font = localization.font(&asset_server);
+    }
+}
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            localized_text_update.run_if(resource_changed::<Localization>()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{Font as _, FontVec};
+
+    use super::*;
+
+    fn load_font(path: &str) -> FontVec {
+        let path = format!("{}/assets/{}", env!("CARGO_MANIFEST_DIR"), path);
+        let data = std::fs::read(&path).unwrap_or_else(|error| panic!("{}: {}", path, error));
+
+        FontVec::try_from_vec(data).unwrap()
+    }
+
+    fn localization(strings: &[(&str, &str)]) -> Localization {
+        let locale = LocaleFile {
+            name: "TEST".to_string(),
+            strings: strings
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect(),
+        };
+
+        Localization {
+            language: Language::English,
+            locale,
+            fallback: LocaleFile::parse(Language::English),
+        }
+    }
+
+    #[test]
+    fn format_fills_placeholders_in_order() {
+        let localization = localization(&[("summary", "{} OF {}")]);
+
+        assert_eq!(localization.format("summary", &[&3, &"TEN"]), "3 OF TEN");
+    }
+
+    #[test]
+    fn format_keeps_placeholders_without_arguments() {
+        let localization = localization(&[("summary", "{}: {} ({}/{})")]);
+
+        assert_eq!(
+            localization.format("summary", &[&"BALL"]),
+            "BALL: {} ({}/{})"
+        );
+        assert_eq!(localization.format("summary", &[]), "{}: {} ({}/{})");
+    }
+
+    #[test]
+    fn format_ignores_extra_arguments() {
+        let localization = localization(&[("score", "SCORE: {}")]);
+
+        assert_eq!(localization.format("score", &[&7, &8]), "SCORE: 7");
+        assert_eq!(localization.format("score_none", &[&7]), "score_none");
+    }
+
+    #[test]
+    fn text_falls_back_to_english() {
+        let localization = localization(&[]);
+
+        assert_eq!(localization.text("main_menu"), "MAIN MENU");
+    }
+
+    #[test]
+    fn every_language_has_the_english_keys() {
+        let english = LocaleFile::parse(Language::English);
+        assert!(!english.strings.is_empty());
+
+        for language in Language::ALL {
+            let locale = LocaleFile::parse(language);
+
+            for (key, text) in &english.strings {
+                let translated = locale
+                    .strings
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{:?} is missing {:?}", language, key));
+
+                assert_eq!(
+                    translated.matches("{}").count(),
+                    text.matches("{}").count(),
+                    "{:?} has different placeholders for {:?}",
+                    language,
+                    key
+                );
+            }
+
+            for key in locale.strings.keys() {
+                assert!(
+                    english.strings.contains_key(key),
+                    "{:?} has unknown key {:?}",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_language_has_a_font_for_its_text() {
+        for language in Language::ALL {
+            let locale = LocaleFile::parse(language);
+            let font = load_font(language.font());
+
+            for character in locale
+                .strings
+                .values()
+                .chain([&locale.name])
+                .flat_map(|text| text.chars())
+            {
+                assert!(
+                    character.is_whitespace() || font.glyph_id(character).0 != 0,
+                    "{:?} can't draw {:?}",
+                    language,
+                    character
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn georgian_needs_the_wider_font() {
+        let noto = load_font(Language::English.font());
+
+        assert_eq!(noto.glyph_id('ქ').0, 0);
+        assert_ne!(Language::Georgian.font(), Language::English.font());
+    }
+}
@@ -7,13 +7,31 @@ use bevy::{
 };
 use bevy_embedded_assets::EmbeddedAssetPlugin;
 use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
+pub mod accessibility;
+pub mod achievements;
+pub mod cosmetics;
 pub mod gameover;
 pub mod ingame;
+pub mod lan;
+pub mod localization;
 pub mod mainmenu;
+pub mod save;
+pub mod stats;
+pub mod widgets;
 
+use accessibility::AccessibilityPlugin;
+use achievements::AchievementsPlugin;
+use cosmetics::CosmeticsPlugin;
 use gameover::GameOverPlugin;
+use ingame::versus::MainCamera;
 use ingame::InGamePlugin;
+use lan::LanPlugin;
+use localization::{Localization, LocalizationPlugin};
 use mainmenu::MainMenuPlugin;
+use save::SaveData;
+use stats::StatsPlugin;
+use widgets::WidgetsPlugin;
 
 pub const SCREEN_WIDTH: f32 = 1280.0;
 pub const SCREEN_HEIGHT: f32 = 720.0;
@@ -24,9 +42,14 @@ pub enum AppState {
     MainMenu,
     InGame,
     GameOver,
+    Stats,
+    Achievements,
+    Customize,
+    Accessibility,
+    Lobby,
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States, Serialize, Deserialize)]
 pub enum GameDifficultyState {
     Easy,
     #[default]
@@ -35,6 +58,8 @@ pub enum GameDifficultyState {
 }
 
 fn main() {
+    let save_data = SaveData::load();
+
     App::new()
         .add_plugins((
             DefaultPlugins
@@ -52,12 +77,23 @@ fn main() {
                 .add_before::<bevy::asset::AssetPlugin, _>(EmbeddedAssetPlugin),
             AudioPlugin,
         ))
+        .insert_resource(save_data.loadout.clone())
+        .insert_resource(save_data.accessibility.clone())
+        .insert_resource(Localization::new(save_data.language))
+        .insert_resource(save_data)
         .add_systems(Startup, setup)
         .add_state::<AppState>()
         .add_state::<GameDifficultyState>()
         .add_plugins(InGamePlugin)
         .add_plugins(MainMenuPlugin)
         .add_plugins(GameOverPlugin)
+        .add_plugins(StatsPlugin)
+        .add_plugins(AchievementsPlugin)
+        .add_plugins(CosmeticsPlugin)
+        .add_plugins(AccessibilityPlugin)
+        .add_plugins(LocalizationPlugin)
+        .add_plugins(LanPlugin)
+        .add_plugins(WidgetsPlugin)
         .run();
 }
 
@@ -73,6 +109,7 @@ pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ..default()
         },
         BloomSettings::default(), // 3. Enable bloom for the camera
+        MainCamera,
     ));
     commands.spawn(SpriteBundle {
         texture: asset_server.load("sprites/menu_background.png"),
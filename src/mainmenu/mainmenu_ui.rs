@@ -1,35 +1,71 @@
 #![allow(clippy::complexity)]
 
 use bevy::prelude::*;
-use bevy_kira_audio::prelude::*;
 
+use crate::accessibility::Accessibility;
+use crate::ingame::ball_types::BallSelection;
+use crate::ingame::daily::DailyChallenge;
+use crate::ingame::rules::GameMode;
+use crate::ingame::versus::Versus;
 use crate::ingame::Scores;
+use crate::localization::Localization;
+use crate::save::SaveData;
+use crate::widgets::set_button_label;
+use crate::widgets::spawn_button;
+use crate::widgets::ButtonPressedEvent;
+use crate::widgets::ButtonSpec;
+use crate::widgets::UiTheme;
 use crate::AppState;
 use crate::GameDifficultyState;
 
 #[derive(Component)]
-pub struct EasyButton;
+pub struct MainMenuEntity;
 
-#[derive(Component)]
-pub struct MediumButton;
+//everything the main menu buttons do besides switching screens
+#[derive(Component, Clone, Copy)]
+pub enum MainMenuAction {
+    Play(GameDifficultyState),
+    Daily,
+    Practice,
+    HotSeat,
+    Versus,
+    Coop,
+    CycleBall,
+    ToggleGhost,
+    ToggleTrajectory,
+    ToggleBankShots,
+}
 
-#[derive(Component)]
-pub struct HardButton;
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+    scores: Res<Scores>,
+    ball_selection: Res<BallSelection>,
+    daily: Res<DailyChallenge>,
+    save_data: Res<SaveData>,
+    accessibility: Res<Accessibility>,
+    localization: Res<Localization>,
+) {
+    let font = localization.font(&asset_server);
 
-#[derive(Component)]
-pub struct MainMenuEntity;
+    let daily_text = match save_data.daily_result(&daily.date) {
+        Some(result) if result.assisted => {
+            localization.format("daily_best_assisted", &[&result.score])
+        }
+        Some(result) => localization.format("daily_best", &[&result.score]),
+        None => localization.text("daily_challenge"),
+    };
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+    let ball_text = localization.format("ball", &[&localization.text(ball_selection.text_key())]);
 
-pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>, scores: Res<Scores>) {
     //version text
     commands
         .spawn(
             TextBundle::from_section(
                 "v1.1",
                 TextStyle {
-                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
+                    font: font.clone(),
                     font_size: 25.0,
                     color: Color::WHITE,
                 },
@@ -93,7 +129,7 @@ pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>, scores: Res
             parent
                 .spawn(NodeBundle {
                     style: Style {
-                        height: Val::Percent(50.0),
+                        height: Val::Percent(40.0),
                         width: Val::Percent(100.0),
                         flex_direction: FlexDirection::Row,
                         // vertically center child text
@@ -105,172 +141,254 @@ pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>, scores: Res
                     },
                     ..default()
                 })
-                //spawn easy button
+                //spawn difficulty buttons
                 .with_children(|parent| {
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                width: Val::Px(220.0),
-                                height: Val::Px(65.0),
-                                border: UiRect::all(Val::Px(5.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            border_color: BorderColor(Color::BLACK),
-                            background_color: NORMAL_BUTTON.into(),
-                            ..default()
-                        })
-                        .insert(EasyButton)
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                format!("EASY: {}", scores.easy_hscore),
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.19, 0.76, 0.41),
-                                },
-                            ));
-                        });
+                    for (difficulty, text) in [
+                        (
+                            GameDifficultyState::Easy,
+                            localization.format("easy_best", &[&scores.easy_hscore]),
+                        ),
+                        (
+                            GameDifficultyState::Medium,
+                            localization.format("medium_best", &[&scores.medium_hscore]),
+                        ),
+                        (
+                            GameDifficultyState::Hard,
+                            localization.format("hard_best", &[&scores.hard_hscore]),
+                        ),
+                    ] {
+                        spawn_button(
+                            parent,
+                            &theme,
+                            &font,
+                            ButtonSpec::new(text)
+                                .size(220.0, 65.0)
+                                .text_color(accessibility.palette.difficulty_color(difficulty)),
+                        )
+                        .insert(MainMenuAction::Play(difficulty));
+                    }
+                });
+        })
+        //options node bundle
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        height: Val::Percent(30.0),
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Start,
+                        align_content: AlignContent::Start,
+                        justify_content: JustifyContent::Center,
+                        flex_wrap: FlexWrap::Wrap,
+                        column_gap: Val::Px(30.0),
+                        row_gap: Val::Px(15.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                //spawn option buttons
+                .with_children(|parent| {
+                    let multiplayer = Color::rgb(0.9, 0.55, 0.2);
 
-                    //spawn medium button
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                width: Val::Px(220.0),
-                                height: Val::Px(65.0),
-                                border: UiRect::all(Val::Px(5.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            border_color: BorderColor(Color::BLACK),
-                            background_color: NORMAL_BUTTON.into(),
-                            ..default()
-                        })
-                        .insert(MediumButton)
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                format!("MEDIUM: {}", scores.medium_hscore),
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.35, 0.67, 0.89),
-                                },
-                            ));
-                        });
+                    for (spec, action, width, color) in [
+                        (
+                            ButtonSpec::new(ball_text),
+                            Some(MainMenuAction::CycleBall),
+                            300.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(daily_text),
+                            Some(MainMenuAction::Daily),
+                            380.0,
+                            Some(Color::rgb(0.95, 0.77, 0.06)),
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("stats")).open(AppState::Stats),
+                            None,
+                            200.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("achievements"))
+                                .open(AppState::Achievements),
+                            None,
+                            280.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("customize"))
+                                .open(AppState::Customize),
+                            None,
+                            240.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("practice")),
+                            Some(MainMenuAction::Practice),
+                            240.0,
+                            Some(Color::rgb(0.19, 0.76, 0.41)),
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("hot_seat")),
+                            Some(MainMenuAction::HotSeat),
+                            200.0,
+                            Some(multiplayer),
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("versus")),
+                            Some(MainMenuAction::Versus),
+                            200.0,
+                            Some(multiplayer),
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("coop")),
+                            Some(MainMenuAction::Coop),
+                            200.0,
+                            Some(multiplayer),
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("lan")).open(AppState::Lobby),
+                            None,
+                            200.0,
+                            Some(multiplayer),
+                        ),
+                        (
+                            ButtonSpec::new(ghost_text(&save_data, &localization)),
+                            Some(MainMenuAction::ToggleGhost),
+                            200.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(localization.text("access"))
+                                .open(AppState::Accessibility),
+                            None,
+                            200.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(trajectory_text(&save_data, &localization)),
+                            Some(MainMenuAction::ToggleTrajectory),
+                            200.0,
+                            None,
+                        ),
+                        (
+                            ButtonSpec::new(bank_shot_text(&save_data, &localization)),
+                            Some(MainMenuAction::ToggleBankShots),
+                            200.0,
+                            None,
+                        ),
+                    ] {
+                        let mut spec = spec.size(width, 55.0).font_size(32.0);
+                        if let Some(color) = color {
+                            spec = spec.text_color(color);
+                        }
 
-                    //spawn hard button
-                    parent
-                        .spawn(ButtonBundle {
-                            style: Style {
-                                width: Val::Px(220.0),
-                                height: Val::Px(65.0),
-                                border: UiRect::all(Val::Px(5.0)),
-                                // horizontally center child text
-                                justify_content: JustifyContent::Center,
-                                // vertically center child text
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            border_color: BorderColor(Color::BLACK),
-                            background_color: NORMAL_BUTTON.into(),
-                            ..default()
-                        })
-                        .insert(HardButton)
-                        .with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                format!("HARD: {}", scores.hard_hscore),
-                                TextStyle {
-                                    font: asset_server.load("fonts/NotoSans-Medium.ttf"),
-                                    font_size: 40.0,
-                                    color: Color::rgb(0.88, 0.21, 0.20),
-                                },
-                            ));
-                        });
+                        let mut button = spawn_button(parent, &theme, &font, spec);
+                        if let Some(action) = action {
+                            button.insert(action);
+                        }
+                    }
                 });
         });
 }
 
-pub fn easy_button_system(
+fn ghost_text(save_data: &SaveData, localization: &Localization) -> String {
+    if save_data.hide_ghost {
+        localization.text("ghost_off")
+    } else {
+        localization.text("ghost_on")
+    }
+}
+
+fn trajectory_text(save_data: &SaveData, localization: &Localization) -> String {
+    if save_data.trajectory_preview {
+        localization.text("arc_on")
+    } else {
+        localization.text("arc_off")
+    }
+}
+
+fn bank_shot_text(save_data: &SaveData, localization: &Localization) -> String {
+    if save_data.bank_shots {
+        localization.text("bank_on")
+    } else {
+        localization.text("bank_off")
+    }
+}
+
+pub fn main_menu_actions(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<EasyButton>),
-    >,
+    daily: Res<DailyChallenge>,
+    localization: Res<Localization>,
+    mut ball_selection: ResMut<BallSelection>,
+    mut save_data: ResMut<SaveData>,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    buttons: Query<(&MainMenuAction, &Children)>,
+    mut texts: Query<&mut Text>,
 ) {
-    for (interaction, mut color, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
+    for event in pressed_reader.iter() {
+        let Ok((action, children)) = buttons.get(event.entity) else {
+            continue;
+        };
+
+        match *action {
+            MainMenuAction::Play(difficulty) => {
+                commands.insert_resource(GameMode::Standard);
                 commands.insert_resource(NextState(Some(AppState::InGame)));
-                commands.insert_resource(NextState(Some(GameDifficultyState::Easy)));
+                commands.insert_resource(NextState(Some(difficulty)));
             }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-                audio.play(asset_server.load("sounds/hover_button.ogg"));
+            MainMenuAction::Daily => {
+                commands.insert_resource(GameMode::Daily);
+                commands.insert_resource(NextState(Some(AppState::InGame)));
+                commands.insert_resource(NextState(Some(daily.difficulty)));
             }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
+            MainMenuAction::Practice => {
+                commands.insert_resource(GameMode::Practice);
+                commands.insert_resource(NextState(Some(AppState::InGame)));
+                commands.insert_resource(NextState(Some(GameDifficultyState::Easy)));
             }
-        }
-    }
-}
-
-pub fn medium_button_system(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<MediumButton>),
-    >,
-) {
-    for (interaction, mut color, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
+            MainMenuAction::HotSeat => {
+                //both players play on the last picked difficulty
+                commands.insert_resource(GameMode::HotSeat);
+                commands.insert_resource(Versus::new_match());
                 commands.insert_resource(NextState(Some(AppState::InGame)));
-                commands.insert_resource(NextState(Some(GameDifficultyState::Medium)));
             }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-                audio.play(asset_server.load("sounds/hover_button.ogg"));
+            MainMenuAction::Versus => {
+                commands.insert_resource(GameMode::SplitScreen);
+                commands.insert_resource(NextState(Some(AppState::InGame)));
             }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
+            MainMenuAction::Coop => {
+                commands.insert_resource(GameMode::Coop);
+                commands.insert_resource(NextState(Some(AppState::InGame)));
             }
-        }
-    }
-}
+            MainMenuAction::CycleBall => {
+                *ball_selection = ball_selection.next();
 
-pub fn hard_button_system(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<HardButton>),
-    >,
-) {
-    for (interaction, mut color, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.insert_resource(NextState(Some(AppState::InGame)));
-                commands.insert_resource(NextState(Some(GameDifficultyState::Hard)))
+                let text =
+                    localization.format("ball", &[&localization.text(ball_selection.text_key())]);
+                set_button_label(children, &mut texts, text);
             }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-                audio.play(asset_server.load("sounds/hover_button.ogg"));
+            MainMenuAction::ToggleGhost => {
+                save_data.hide_ghost = !save_data.hide_ghost;
+                save_data.store();
+
+                set_button_label(children, &mut texts, ghost_text(&save_data, &localization));
             }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
+            MainMenuAction::ToggleTrajectory => {
+                save_data.trajectory_preview = !save_data.trajectory_preview;
+                save_data.store();
+
+                let text = trajectory_text(&save_data, &localization);
+                set_button_label(children, &mut texts, text);
+            }
+            MainMenuAction::ToggleBankShots => {
+                save_data.bank_shots = !save_data.bank_shots;
+                save_data.store();
+
+                let text = bank_shot_text(&save_data, &localization);
+                set_button_label(children, &mut texts, text);
             }
         }
     }
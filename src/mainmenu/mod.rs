@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 mod mainmenu_ui;
 
+use crate::ingame::daily::daily_refresh;
 use crate::AppState;
 use mainmenu_ui::*;
 
@@ -9,12 +10,14 @@ pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MainMenu), setup)
-            .add_systems(
-                Update,
-                (easy_button_system, medium_button_system, hard_button_system)
-                    .run_if(in_state(AppState::MainMenu)),
-            )
-            .add_systems(OnExit(AppState::MainMenu), entity_despawner);
+        app.add_systems(
+            OnEnter(AppState::MainMenu),
+            (daily_refresh, setup.after(daily_refresh)),
+        )
+        .add_systems(
+            Update,
+            main_menu_actions.run_if(in_state(AppState::MainMenu)),
+        )
+        .add_systems(OnExit(AppState::MainMenu), entity_despawner);
     }
 }
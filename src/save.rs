@@ -0,0 +1,96 @@
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::Accessibility;
+use crate::achievements::Achievement;
+use crate::cosmetics::Loadout;
+use crate::ingame::ball_types::BallSelection;
+use crate::ingame::ghost::GhostRun;
+use crate::ingame::run_stats::LifetimeStats;
+use crate::localization::Language;
+use crate::GameDifficultyState;
+
+const SAVE_FILE: &str = "save.ron";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub date: String,
+    pub score: i32,
+    pub difficulty: GameDifficultyState,
+    pub ball: BallSelection,
+    //played with aim assist
+    #[serde(default)]
+    pub assisted: bool,
+}
+
+//everything that outlives the game process, stored in a single ron file
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    pub daily_results: Vec<DailyResult>,
+    pub lifetime_stats: LifetimeStats,
+    pub achievements: Vec<Achievement>,
+    pub loadout: Loadout,
+    //best run per difficulty, raced by the ghost
+    pub ghosts: Vec<GhostRun>,
+    pub hide_ghost: bool,
+    //no screen shake or zoom for players sensitive to motion
+    pub reduce_motion: bool,
+    //assist that draws the ball's predicted arc, runs with it are unranked
+    pub trajectory_preview: bool,
+    //bonus points for balls that hit a wall right after a shot
+    pub bank_shots: bool,
+    pub accessibility: Accessibility,
+    pub language: Language,
+}
+
+impl SaveData {
+    pub fn load() -> Self {
+        let path = save_path();
+
+        match fs::read_to_string(&path) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|error| {
+                warn!("Save file {:?} is broken, starting fresh: {}", path, error);
+                SaveData::default()
+            }),
+            //first launch, nothing saved yet
+            Err(_) => SaveData::default(),
+        }
+    }
+
+    pub fn store(&self) {
+        let path = save_path();
+
+        let content = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(content) => content,
+            Err(error) => {
+                error!("Couldn't serialize save data: {}", error);
+                return;
+            }
+        };
+
+        if let Err(error) = fs::write(&path, content) {
+            error!("Couldn't write save file {:?}: {}", path, error);
+        }
+    }
+
+    pub fn daily_result(&self, date: &str) -> Option<&DailyResult> {
+        self.daily_results.iter().find(|result| result.date == date)
+    }
+
+    pub fn ghost(&self, difficulty: GameDifficultyState) -> Option<&GhostRun> {
+        self.ghosts
+            .iter()
+            .find(|ghost| ghost.difficulty == difficulty)
+    }
+}
+
+//keep the save next to the executable so it travels with the game folder
+fn save_path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(SAVE_FILE)))
+        .unwrap_or_else(|| PathBuf::from(SAVE_FILE))
+}
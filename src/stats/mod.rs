@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+mod stats_ui;
+
+use crate::AppState;
+use stats_ui::*;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Stats), setup)
+            .add_systems(OnExit(AppState::Stats), entity_despawner);
+    }
+}
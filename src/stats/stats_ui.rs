@@ -0,0 +1,110 @@
+#![allow(clippy::complexity)]
+
+use bevy::prelude::*;
+
+use crate::ingame::run_stats::format_time;
+use crate::localization::Localization;
+use crate::save::SaveData;
+use crate::widgets::spawn_button;
+use crate::widgets::text;
+use crate::widgets::title_panel;
+use crate::widgets::ButtonSpec;
+use crate::widgets::UiTheme;
+use crate::AppState;
+
+#[derive(Component)]
+pub struct StatsEntity;
+
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    theme: Res<UiTheme>,
+    save_data: Res<SaveData>,
+) {
+    info!("Stats menu activated");
+
+    let font = localization.font(&asset_server);
+
+    let stats = &save_data.lifetime_stats;
+    let lines = [
+        localization.format("games_played", &[&stats.games_played]),
+        localization.format("best_score", &[&stats.best_score]),
+        localization.format("assisted_best", &[&stats.assisted_best_score]),
+        localization.format("shots_fired", &[&stats.shots_fired]),
+        localization.format("hits", &[&stats.hits]),
+        localization.format("accuracy", &[&format!("{:.1}", stats.accuracy())]),
+        localization.format("longest_run", &[&format_time(stats.longest_run_time)]),
+        localization.format("easy_kick_ups", &[&stats.easy_kick_ups]),
+        localization.format("medium_kick_ups", &[&stats.medium_kick_ups]),
+        localization.format("hard_kick_ups", &[&stats.hard_kick_ups]),
+    ];
+
+    //create full screen node bundle
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Percent(100.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(25.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(StatsEntity)
+        //create yellow title background
+        .with_children(|parent| {
+            parent
+                .spawn(title_panel(&theme, 800.0, 100.0))
+                //"stats" text
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        localization.text("stats"),
+                        &font,
+                        120.0,
+                        theme.title_text,
+                    ));
+                });
+        })
+        //create black stats background
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(800.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(15.0)),
+                        ..default()
+                    },
+                    background_color: Color::BLACK.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for line in lines {
+                        parent.spawn(text(line, &font, 32.0, Color::WHITE));
+                    }
+                });
+        })
+        //spawn "main menu" button
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                &theme,
+                &font,
+                ButtonSpec::new(localization.text("main_menu")).open(AppState::MainMenu),
+            );
+        });
+}
+
+pub fn entity_despawner(mut entities: Query<Entity, With<StatsEntity>>, mut commands: Commands) {
+    info!("Stats Despawner Activated");
+
+    //despawn everyting in Stats
+    for entities_despawner in &mut entities {
+        commands.entity(entities_despawner).despawn_recursive();
+    }
+}
@@ -0,0 +1,392 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use bevy_kira_audio::prelude::*;
+
+use crate::localization::LocalizedText;
+use crate::AppState;
+
+#[derive(Component)]
+pub struct MenuButton {
+    //presses the button from anywhere on the screen
+    pub shortcut: Option<KeyCode>,
+}
+
+//switches screens when pressed, every screen switch works the same
+#[derive(Component)]
+pub struct OpenScreen(pub AppState);
+
+//screens put their own action component on a button and look it up from `entity`
+#[derive(Event)]
+pub struct ButtonPressedEvent {
+    pub entity: Entity,
+}
+
+//colors and sounds shared by every menu
+#[derive(Resource, Clone)]
+pub struct UiTheme {
+    pub button: Color,
+    pub button_focused: Color,
+    pub border: Color,
+    pub border_focused: Color,
+    pub border_width: f32,
+    pub text: Color,
+    pub title_panel: Color,
+    pub title_text: Color,
+    pub focus_sound: &'static str,
+    pub press_sound: &'static str,
+}
+
+impl Default for UiTheme {
+    fn default() -> Self {
+        Self {
+            button: Color::rgb(0.15, 0.15, 0.15),
+            button_focused: Color::rgb(0.25, 0.25, 0.25),
+            border: Color::BLACK,
+            border_focused: Color::WHITE,
+            border_width: 5.0,
+            text: Color::rgb(0.9, 0.9, 0.9),
+            title_panel: Color::YELLOW,
+            title_text: Color::BLACK,
+            focus_sound: "sounds/hover_button.ogg",
+            press_sound: "sounds/click.ogg",
+        }
+    }
+}
+
+//the button drawn highlighted, picked by hovering it or with the keyboard
+#[derive(Resource, Default)]
+pub struct UiFocus(pub Option<Entity>);
+
+pub struct ButtonSpec {
+    label: String,
+    width: f32,
+    height: f32,
+    font_size: f32,
+    text_color: Option<Color>,
+    localized: Option<&'static str>,
+    shortcut: Option<KeyCode>,
+    open: Option<AppState>,
+}
+
+impl ButtonSpec {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            width: 200.0,
+            height: 65.0,
+            font_size: 40.0,
+            text_color: None,
+            localized: None,
+            shortcut: None,
+            open: None,
+        }
+    }
+
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    //label follows the language when it changes on screen
+    pub fn localized(mut self, key: &'static str) -> Self {
+        self.localized = Some(key);
+        self
+    }
+
+    pub fn shortcut(mut self, key: KeyCode) -> Self {
+        self.shortcut = Some(key);
+        self
+    }
+
+    pub fn open(mut self, state: AppState) -> Self {
+        self.open = Some(state);
+        self
+    }
+}
+
+pub fn spawn_button<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &UiTheme,
+    font: &Handle<Font>,
+    spec: ButtonSpec,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut button = parent.spawn(ButtonBundle {
+        style: Style {
+            width: Val::Px(spec.width),
+            height: Val::Px(spec.height),
+            border: UiRect::all(Val::Px(theme.border_width)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        border_color: BorderColor(theme.border),
+        background_color: theme.button.into(),
+        ..default()
+    });
+
+    button
+        .insert(MenuButton {
+            shortcut: spec.shortcut,
+        })
+        .with_children(|parent| {
+            let mut label = parent.spawn(text(
+                spec.label,
+                font,
+                spec.font_size,
+                spec.text_color.unwrap_or(theme.text),
+            ));
+
+            if let Some(key) = spec.localized {
+                label.insert(LocalizedText(key));
+            }
+        });
+
+    if let Some(state) = spec.open {
+        button.insert(OpenScreen(state));
+    }
+
+    button
+}
+
+//the yellow banner every screen opens with
+pub fn title_panel(theme: &UiTheme, width: f32, height: f32) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Px(width),
+            height: Val::Px(height),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        background_color: theme.title_panel.into(),
+        ..default()
+    }
+}
+
+pub fn text(
+    value: impl Into<String>,
+    font: &Handle<Font>,
+    font_size: f32,
+    color: Color,
+) -> TextBundle {
+    TextBundle::from_section(
+        value,
+        TextStyle {
+            font: font.clone(),
+            font_size,
+            color,
+        },
+    )
+}
+
+//replaces the text of a button's label
+pub fn set_button_label(children: &Children, texts: &mut Query<&mut Text>, value: String) {
+    for &child in children.iter() {
+        if let Ok(mut text) = texts.get_mut(child) {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub fn button_interaction(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    theme: Res<UiTheme>,
+    mut focus: ResMut<UiFocus>,
+    buttons: Query<(Entity, &Interaction, &MenuButton), Changed<Interaction>>,
+    mut pressed_writer: EventWriter<ButtonPressedEvent>,
+) {
+    for (entity, interaction, _) in &buttons {
+        match *interaction {
+            Interaction::Pressed => {
+                audio.play(asset_server.load(theme.press_sound));
+                pressed_writer.send(ButtonPressedEvent { entity });
+            }
+            Interaction::Hovered => {
+                //leaving the pressed state keeps the hover, don't beep twice
+                if focus.0 != Some(entity) {
+                    focus.0 = Some(entity);
+                    audio.play(asset_server.load(theme.focus_sound));
+                }
+            }
+            Interaction::None => {
+                if focus.0 == Some(entity) {
+                    focus.0 = None;
+                }
+            }
+        }
+    }
+}
+
+//tab walks the buttons in reading order, the arrows jump to the closest one that way
+pub fn button_keyboard_focus(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    theme: Res<UiTheme>,
+    keyboard: Res<Input<KeyCode>>,
+    mut focus: ResMut<UiFocus>,
+    buttons: Query<(Entity, &GlobalTransform), With<MenuButton>>,
+) {
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let direction = if keyboard.just_pressed(KeyCode::Tab) {
+        None
+    } else if keyboard.just_pressed(KeyCode::Up) {
+        Some(Vec2::NEG_Y)
+    } else if keyboard.just_pressed(KeyCode::Down) {
+        Some(Vec2::Y)
+    } else if keyboard.just_pressed(KeyCode::Left) {
+        Some(Vec2::NEG_X)
+    } else if keyboard.just_pressed(KeyCode::Right) {
+        Some(Vec2::X)
+    } else {
+        return;
+    };
+
+    //ui positions grow downwards, so sorting by y then x is reading order
+    let mut order: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation().truncate()))
+        .collect();
+    order.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let current = focus
+        .0
+        .and_then(|focused| order.iter().position(|(entity, _)| *entity == focused));
+
+    //the first key press only picks a starting point
+    let Some(current) = current else {
+        if let Some((first, _)) = order.first() {
+            focus.0 = Some(*first);
+            audio.play(asset_server.load(theme.focus_sound));
+        }
+        return;
+    };
+
+    let next = match direction {
+        None if shift => order[(current + order.len() - 1) % order.len()].0,
+        None => order[(current + 1) % order.len()].0,
+        Some(direction) => {
+            let position = order[current].1;
+            let closest = order
+                .iter()
+                .filter_map(|(entity, other)| {
+                    let offset = *other - position;
+                    let along = offset.dot(direction);
+                    //buttons off to the side count as further away
+                    (along > 1.0)
+                        .then_some((*entity, along + offset.perp_dot(direction).abs() * 2.0))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            match closest {
+                Some((entity, _)) => entity,
+                None => return,
+            }
+        }
+    };
+
+    if focus.0 != Some(next) {
+        focus.0 = Some(next);
+        audio.play(asset_server.load(theme.focus_sound));
+    }
+}
+
+//enter presses the focused button, shortcut keys press theirs directly
+pub fn button_activate(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    theme: Res<UiTheme>,
+    keyboard: Res<Input<KeyCode>>,
+    mut focus: ResMut<UiFocus>,
+    buttons: Query<(Entity, &MenuButton)>,
+    mut pressed_writer: EventWriter<ButtonPressedEvent>,
+) {
+    let enter = keyboard.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]);
+
+    for (entity, button) in &buttons {
+        let focused = enter && focus.0 == Some(entity);
+        let shortcut = button
+            .shortcut
+            .is_some_and(|key| keyboard.just_pressed(key));
+
+        if focused || shortcut {
+            focus.0 = Some(entity);
+            audio.play(asset_server.load(theme.press_sound));
+            pressed_writer.send(ButtonPressedEvent { entity });
+            return;
+        }
+    }
+}
+
+pub fn button_focus_style(
+    theme: Res<UiTheme>,
+    focus: Res<UiFocus>,
+    mut buttons: Query<(Entity, &mut BackgroundColor, &mut BorderColor), With<MenuButton>>,
+) {
+    for (entity, mut color, mut border_color) in &mut buttons {
+        let focused = focus.0 == Some(entity);
+        let (background, border) = if focused {
+            (theme.button_focused, theme.border_focused)
+        } else {
+            (theme.button, theme.border)
+        };
+
+        //only touch the colors when they change
+        if color.0 != background {
+            color.0 = background;
+        }
+        if border_color.0 != border {
+            border_color.0 = border;
+        }
+    }
+}
+
+pub fn button_navigation(
+    mut commands: Commands,
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    buttons: Query<&OpenScreen>,
+) {
+    for event in pressed_reader.iter() {
+        if let Ok(OpenScreen(state)) = buttons.get(event.entity) {
+            commands.insert_resource(NextState(Some(*state)));
+        }
+    }
+}
+
+//a new screen starts without a focused button
+pub fn focus_reset(mut focus: ResMut<UiFocus>) {
+    focus.0 = None;
+}
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>()
+            .init_resource::<UiFocus>()
+            .add_event::<ButtonPressedEvent>()
+            .add_systems(
+                Update,
+                (
+                    focus_reset.run_if(state_changed::<AppState>()),
+                    button_interaction.after(focus_reset),
+                    button_keyboard_focus.after(button_interaction),
+                    button_activate.after(button_keyboard_focus),
+                    button_focus_style.after(button_activate),
+                    button_navigation.after(button_activate),
+                ),
+            );
+    }
+}